@@ -40,6 +40,8 @@ interface CredentialFormData {
   role: string;
   validityDays: string;
   aiConfidence: number;
+  aiAssessmentId: string;
+  schemaId: string;
   description: string;
   additionalMetadata: string;
 }
@@ -57,6 +59,8 @@ export default function IssuerDashboard() {
     role: '',
     validityDays: '30',
     aiConfidence: 85,
+    aiAssessmentId: '',
+    schemaId: '0',
     description: '',
     additionalMetadata: '{}'
   });
@@ -246,6 +250,15 @@ const issueCredential = async () => {
     return;
   }
 
+  if (!/^\d+$/.test(credentialForm.aiAssessmentId)) {
+    setIssuanceMessage({
+      type: 'error',
+      text: 'AI assessment missing',
+      details: 'Enter the id of the assessment the AI oracle submitted for this credential.'
+    });
+    return;
+  }
+
   try {
     setIssuanceLoading(true);
     setIssuanceMessage({
@@ -311,9 +324,10 @@ const issueCredential = async () => {
     // Generate issuer signature (128 hex chars minimum)
     const issuerSignature = generateIssuerSignature(credentialData, walletState.publicKey);
 
-    // Generate DIDs
-    const issuerDID = `did:casper:${walletState.publicKey.slice(0, 20)}`;
-    const holderDID = `did:casper:${selectedRequest.recipientPublicKey.slice(0, 20)}`;
+    // Generate DIDs (did:casper:<account hash>, the DID each account creates for itself)
+    const holderPublicKey = CLPublicKey.fromHex(selectedRequest.recipientPublicKey);
+    const issuerDID = accountDid(CLPublicKey.fromHex(walletState.publicKey));
+    const holderDID = accountDid(holderPublicKey);
 
     console.log('🔐 Cryptographic proofs generated:', {
       credentialHash: credentialHash.slice(0, 20) + '...',
@@ -328,9 +342,6 @@ const issueCredential = async () => {
       text: '⛓️ Step 4/5: Preparing blockchain transaction...'
     });
 
-    // CRITICAL FIX: Convert holder public key to Address (Key type)
-    const holderPublicKey = CLPublicKey.fromHex(selectedRequest.recipientPublicKey);
-    
     // Prepare runtime args for contract - CORRECTED VERSION
     const runtimeArgs = RuntimeArgs.fromMap({
       'credential_id': CLValueBuilder.string(generatedCredentialId),
@@ -340,8 +351,9 @@ const issueCredential = async () => {
       'credential_hash': CLValueBuilder.string(credentialHash),
       'issuer_signature': CLValueBuilder.string(issuerSignature),
       'ipfs_hash': CLValueBuilder.string(uploadedIpfsHash),
-      'ai_confidence': CLValueBuilder.u8(credentialForm.aiConfidence),
-      'expires_in_days': CLValueBuilder.u64(parseInt(credentialForm.validityDays))
+      'ai_assessment_id': CLValueBuilder.u32(parseInt(credentialForm.aiAssessmentId)),
+      'expires_in_days': CLValueBuilder.u64(parseInt(credentialForm.validityDays)),
+      'schema_id': CLValueBuilder.u32(parseInt(credentialForm.schemaId))
     });
 
     // Use CONTRACT_HASH from constants
@@ -381,8 +393,9 @@ const issueCredential = async () => {
       holderAddress: 'Key::Account',  // This is now correct
       credentialHash: credentialHash.slice(0, 20) + '...',
       ipfsHash: uploadedIpfsHash,
-      aiConfidence: credentialForm.aiConfidence,
-      expiresInDays: credentialForm.validityDays
+      aiAssessmentId: credentialForm.aiAssessmentId,
+      expiresInDays: credentialForm.validityDays,
+      schemaId: credentialForm.schemaId
     });
 
     // Step 5: Sign deploy with wallet
@@ -501,6 +514,12 @@ const generateCredentialHash = async (credentialData: any): Promise<string> => {
   return hashHex; // 64 hex characters
 };
 
+// Helper function to derive an account's did:casper DID (the hex of its account hash)
+const accountDid = (publicKey: CLPublicKey): string => {
+  const accountHash = Array.from(publicKey.toAccountHash()).map(b => b.toString(16).padStart(2, '0')).join('');
+  return `did:casper:${accountHash}`;
+};
+
 // Helper function to generate issuer signature
 const generateIssuerSignature = (credentialData: any, issuerPublicKey: string): string => {
   // In production, this should use the issuer's private key to sign
@@ -993,25 +1012,35 @@ const generateIssuerSignature = (credentialData: any, issuerPublicKey: string):
                     
                     <div>
                       <label className="block text-sm font-medium text-gray-700 mb-1">
-                        AI Confidence
+                        AI Assessment ID
                       </label>
-                      <div className="flex items-center">
-                        <input
-                          type="range"
-                          min="0"
-                          max="100"
-                          value={credentialForm.aiConfidence}
-                          onChange={(e) => setCredentialForm(prev => ({ ...prev, aiConfidence: parseInt(e.target.value) }))}
-                          disabled={issuanceLoading}
-                          className="flex-1 h-2 bg-gray-200 rounded-lg appearance-none cursor-pointer disabled:opacity-50"
-                        />
-                        <span className="ml-3 text-lg font-semibold text-gray-700 min-w-[45px]">
-                          {credentialForm.aiConfidence}%
-                        </span>
-                      </div>
+                      <input
+                        type="number"
+                        min="0"
+                        value={credentialForm.aiAssessmentId}
+                        onChange={(e) => setCredentialForm(prev => ({ ...prev, aiAssessmentId: e.target.value }))}
+                        disabled={issuanceLoading}
+                        className="w-full border border-gray-300 rounded-lg p-2 focus:ring-2 focus:ring-blue-500 focus:border-transparent disabled:bg-gray-100 text-sm"
+                        placeholder="Submitted by the AI oracle"
+                      />
                     </div>
                   </div>
                   
+                  <div>
+                    <label className="block text-sm font-medium text-gray-700 mb-1">
+                      Schema ID
+                    </label>
+                    <input
+                      type="number"
+                      min="0"
+                      value={credentialForm.schemaId}
+                      onChange={(e) => setCredentialForm(prev => ({ ...prev, schemaId: e.target.value }))}
+                      disabled={issuanceLoading}
+                      className="w-full border border-gray-300 rounded-lg p-2 focus:ring-2 focus:ring-blue-500 focus:border-transparent disabled:bg-gray-100 text-sm"
+                      placeholder="Registered credential schema"
+                    />
+                  </div>
+                  
                   <div>
                     <label className="block text-sm font-medium text-gray-700 mb-1">
                       Description
@@ -1150,6 +1150,15 @@ function decodeCredentialBytes(hexBytes) {
       pos += count;
     }
 
+    function readFixedHex(count) {
+      if (pos + count > bytes.length) {
+        throw new Error(`Cannot read ${count} bytes at position ${pos}, buffer length ${bytes.length}`);
+      }
+      const hex = bytes.slice(pos, pos + count).toString('hex');
+      pos += count;
+      return hex;
+    }
+
     function readKey() {
       // Read Key type (1 byte tag + 32 bytes hash)
       if (pos + 33 > bytes.length) {
@@ -1174,8 +1183,9 @@ function decodeCredentialBytes(hexBytes) {
     console.log(`   ↳ Holder DID: ${holder_did}`);
     const holder_address = readKey();
     console.log(`   ↳ Holder Addr: ${holder_address}`);
-    const credential_hash = readString();
-    const issuer_signature = readString().substring(0, 20) + "...";
+    // Stored as raw bytes: 32-byte hash, then algorithm tag + 64-byte signature
+    const credential_hash = readFixedHex(32);
+    const issuer_signature = readFixedHex(65).substring(0, 20) + "...";
     const issued_at = readU64();
     const expires_at = readU64();
     const ai_confidence = readU8();
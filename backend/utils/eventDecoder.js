@@ -235,7 +235,8 @@ function decodeAuditLogCreated(bytes, offset) {
 
 /**
  * Decode CredentialIssued event
- * Structure: credential_id, holder, issuer, issuer_did, holder_did, ai_confidence, credential_hash, ipfs_hash, timestamp
+ * Structure: credential_id, holder, issuer, issuer_did, holder_did, ai_confidence, credential_hash, ipfs_hash, timestamp, schema_id,
+ *            ai_model_id, ai_model_version, ai_assessment_hash, ai_recommendation, ai_assessment_id
  */
 function decodeCredentialIssued(bytes, offset) {
     let pos = offset;
@@ -287,6 +288,37 @@ function decodeCredentialIssued(bytes, offset) {
     result.timestamp_formatted = timestampResult.value > 0
         ? new Date(timestampResult.value).toISOString()
         : 'Unknown';
+    pos += timestampResult.bytesRead;
+
+    // schema_id: U32 (absent in events from older deployments)
+    if (pos + 4 <= bytes.length) {
+        result.schema_id = readU32(bytes, pos).value;
+        pos += 4;
+    }
+
+    // AI provenance: model id, model version, assessment hash, Option<recommendation>
+    if (pos + 4 <= bytes.length) {
+        for (const field of ['ai_model_id', 'ai_model_version', 'ai_assessment_hash']) {
+            const fieldResult = readString(bytes, pos);
+            result[field] = fieldResult.value;
+            pos += fieldResult.bytesRead;
+        }
+
+        const hasRecommendation = readU8(bytes, pos);
+        pos += hasRecommendation.bytesRead;
+        result.ai_recommendation = null;
+        if (hasRecommendation.value === 1) {
+            const recommendation = readU8(bytes, pos);
+            result.ai_recommendation = ['APPROVE', 'REVIEW', 'REJECT'][recommendation.value] || null;
+            pos += recommendation.bytesRead;
+        }
+    }
+
+    // ai_assessment_id: U32 (absent in events from older deployments)
+    if (pos + 4 <= bytes.length) {
+        result.ai_assessment_id = readU32(bytes, pos).value;
+        pos += 4;
+    }
 
     return result;
 }
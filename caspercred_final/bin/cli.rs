@@ -1,26 +1,31 @@
-//! This example demonstrates how to use the `odra-cli` tool to deploy and interact with a smart contract.
+//! Odra CLI for deploying `CasperCredIQ` and exporting its revocation status
+//! as a W3C `BitstringStatusListCredential`.
 
-use caspercred_final::flipper::Flipper;
+use caspercred_final::status_list_export::status_list_credential;
+use caspercred_final::CasperCredIQ;
 use odra::host::{HostEnv, NoArgs};
 use odra::schema::casper_contract_schema::NamedCLType;
 use odra_cli::{
     deploy::DeployScript,
     scenario::{Args, Error, Scenario, ScenarioMetadata},
     CommandArg, ContractProvider, DeployedContractsContainer, DeployerExt,
-    OdraCli, 
+    OdraCli,
 };
 
-/// Deploys the `Flipper` and adds it to the container.
-pub struct FlipperDeployScript;
+/// Bytes requested per `get_status_list_chunk` call (the contract's cap)
+const STATUS_CHUNK_BYTES: u32 = 1024;
 
-impl DeployScript for FlipperDeployScript {
+/// Deploys `CasperCredIQ` and adds it to the container.
+pub struct CasperCredIQDeployScript;
+
+impl DeployScript for CasperCredIQDeployScript {
     fn deploy(
         &self,
         env: &HostEnv,
         container: &mut DeployedContractsContainer
     ) -> Result<(), odra_cli::deploy::Error> {
-        let _flipper = Flipper::load_or_deploy(
-            &env,
+        let _contract = CasperCredIQ::load_or_deploy(
+            env,
             NoArgs,
             container,
             350_000_000_000 // Adjust gas limit as needed
@@ -30,16 +35,26 @@ impl DeployScript for FlipperDeployScript {
     }
 }
 
-/// Scenario that flips the state of the deployed `Flipper` contract a specified number of times.
-pub struct FlippingScenario;
+/// Reads the revocation bitstring of the deployed contract and prints an
+/// (unsigned) `BitstringStatusListCredential` for the issuer to sign.
+pub struct StatusListScenario;
 
-impl Scenario for FlippingScenario {
+impl Scenario for StatusListScenario {
     fn args(&self) -> Vec<CommandArg> {
-        vec![CommandArg::new(
-            "number",
-            "The number of times to flip the state",
-            NamedCLType::U64,
-        )]
+        vec![
+            CommandArg::new(
+                "id",
+                "URL the status list credential will be published at",
+                NamedCLType::String,
+            )
+            .required(),
+            CommandArg::new(
+                "issuer",
+                "DID of the issuer signing the status list credential",
+                NamedCLType::String,
+            )
+            .required(),
+        ]
     }
 
     fn run(
@@ -48,31 +63,38 @@ impl Scenario for FlippingScenario {
         container: &DeployedContractsContainer,
         args: Args
     ) -> Result<(), Error> {
-        let mut contract = container.contract_ref::<Flipper>(env)?;
-        let n = args.get_single::<u64>("name")?;
+        let contract = container.contract_ref::<CasperCredIQ>(env)?;
+        let id = args.get_single::<String>("id")?;
+        let issuer = args.get_single::<String>("issuer")?;
 
-        env.set_gas(50_000_000);
-        for _ in 0..n {
-            contract.try_flip()?;
+        let mut bitstring = Vec::new();
+        loop {
+            let chunk = contract.get_status_list_chunk(bitstring.len() as u32, STATUS_CHUNK_BYTES);
+            if chunk.is_empty() {
+                break;
+            }
+            bitstring.extend_from_slice(&chunk);
         }
 
+        let credential = status_list_credential(&id, &issuer, &bitstring);
+        println!("{}", serde_json::to_string_pretty(&credential).expect("JSON serialization"));
         Ok(())
     }
 }
 
-impl ScenarioMetadata for FlippingScenario {
-    const NAME: &'static str = "flip";
+impl ScenarioMetadata for StatusListScenario {
+    const NAME: &'static str = "status-list";
     const DESCRIPTION: &'static str =
-        "Flips the state of the deployed flipper contract a specified number of times";
+        "Prints a BitstringStatusListCredential built from the contract's revocation bits";
 }
 
 /// Main function to run the CLI tool.
 pub fn main() {
     OdraCli::new()
         .about("CLI tool for caspercred_final smart contract")
-        .deploy(FlipperDeployScript)
-        .contract::<Flipper>()
-        .scenario(FlippingScenario)
+        .deploy(CasperCredIQDeployScript)
+        .contract::<CasperCredIQ>()
+        .scenario(StatusListScenario)
         .build()
         .run();
 }
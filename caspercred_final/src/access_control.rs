@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+use odra::prelude::*;
+
+use crate::Error;
+
+// ================ ROLES ================
+
+/// Named roles an address can hold. An address may hold several at once.
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum Role {
+    /// May issue credentials.
+    Issuer = 0,
+    /// Recognised verifier (informational, for integrators).
+    Verifier = 1,
+    /// May view every credential.
+    Auditor = 2,
+    /// May grant and revoke the non-admin roles.
+    Admin = 3,
+    /// May pause the contract in an emergency.
+    Guardian = 4,
+    /// Approves or rejects credentials held for human review.
+    Reviewer = 5,
+    /// Posts AI assessments that issuance must reference.
+    AiOracle = 6,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::Issuer,
+        Role::Verifier,
+        Role::Auditor,
+        Role::Admin,
+        Role::Guardian,
+        Role::Reviewer,
+        Role::AiOracle,
+    ];
+
+    fn flag(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// Roles implied by each legacy access level (levels are cumulative).
+fn roles_for_level(level: u8) -> u8 {
+    let mut mask = 0;
+    if level >= 1 {
+        mask |= Role::Verifier.flag();
+    }
+    if level >= 2 {
+        mask |= Role::Issuer.flag();
+    }
+    if level >= 3 {
+        mask |= Role::Auditor.flag();
+    }
+    if level >= 4 {
+        mask |= Role::Admin.flag();
+    }
+    mask
+}
+
+/// Roles managed through the legacy access levels.
+fn level_roles_mask() -> u8 {
+    roles_for_level(4)
+}
+
+/// Roles making up the preset for a legacy access level.
+pub fn level_roles(level: u8) -> Vec<Role> {
+    let mask = roles_for_level(level);
+    Role::ALL
+        .iter()
+        .copied()
+        .filter(|role| mask & role.flag() != 0)
+        .collect()
+}
+
+/// A role held by an address, with its optional end date
+#[odra::odra_type]
+pub struct RoleGrant {
+    pub role: Role,
+    pub valid_until: Option<u64>,
+    pub active: bool,
+}
+
+// ================ MODULE ================
+
+/// Role storage (roles held by an address, combined into one bitmask).
+/// A grant past its `valid_until` is treated as not held.
+#[odra::module]
+pub struct AccessControl {
+    roles: Mapping<Address, u8>,
+    role_expiry: Mapping<(Address, Role), Option<u64>>,
+}
+
+#[odra::module]
+impl AccessControl {
+    pub fn has_role(&self, account: &Address, role: Role) -> bool {
+        self.active_mask(account) & role.flag() != 0
+    }
+
+    pub fn roles_of(&self, account: &Address) -> Vec<Role> {
+        let mask = self.active_mask(account);
+        Role::ALL
+            .iter()
+            .copied()
+            .filter(|role| mask & role.flag() != 0)
+            .collect()
+    }
+
+    /// Every stored grant for `account`, including lapsed ones.
+    pub fn grants_of(&self, account: &Address) -> Vec<RoleGrant> {
+        let mask = self.roles.get(account).unwrap_or(0);
+        let active = self.active_mask(account);
+        Role::ALL
+            .iter()
+            .copied()
+            .filter(|role| mask & role.flag() != 0)
+            .map(|role| RoleGrant {
+                role,
+                valid_until: self.expiry_of(account, role),
+                active: active & role.flag() != 0,
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the role was not held before or its end date changed.
+    pub fn grant(&mut self, account: &Address, role: Role, valid_until: Option<u64>) -> bool {
+        self.check_valid_until(valid_until);
+
+        let changed = !self.has_role(account, role) || self.expiry_of(account, role) != valid_until;
+        let mask = self.roles.get(account).unwrap_or(0);
+        self.roles.set(account, mask | role.flag());
+        self.role_expiry.set(&(*account, role), valid_until);
+        changed
+    }
+
+    /// Returns `true` if the role was held before.
+    pub fn revoke(&mut self, account: &Address, role: Role) -> bool {
+        let held = self.has_role(account, role);
+        let mask = self.roles.get(account).unwrap_or(0);
+        self.roles.set(account, mask & !role.flag());
+        held
+    }
+
+    /// Legacy 0-4 level derived from the highest level-managed role held.
+    pub fn access_level(&self, account: &Address) -> u8 {
+        if self.has_role(account, Role::Admin) {
+            4
+        } else if self.has_role(account, Role::Auditor) {
+            3
+        } else if self.has_role(account, Role::Issuer) {
+            2
+        } else if self.has_role(account, Role::Verifier) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Replaces the level-managed roles with the preset for `level`, all
+    /// ending at `valid_until`. Roles outside the legacy levels (e.g.
+    /// Guardian) are kept. Returns the roles granted and revoked by the change.
+    pub fn set_access_level(
+        &mut self,
+        account: &Address,
+        level: u8,
+        valid_until: Option<u64>,
+    ) -> (Vec<Role>, Vec<Role>) {
+        self.check_valid_until(valid_until);
+
+        let old_mask = self.active_mask(account);
+        let new_mask = (self.roles.get(account).unwrap_or(0) & !level_roles_mask()) | roles_for_level(level);
+        self.roles.set(account, new_mask);
+
+        for role in level_roles(level) {
+            self.role_expiry.set(&(*account, role), valid_until);
+        }
+
+        let granted = Role::ALL
+            .iter()
+            .copied()
+            .filter(|role| old_mask & role.flag() == 0 && new_mask & role.flag() != 0)
+            .collect();
+        let revoked = Role::ALL
+            .iter()
+            .copied()
+            .filter(|role| old_mask & role.flag() != 0 && new_mask & role.flag() == 0)
+            .collect();
+        (granted, revoked)
+    }
+
+    fn expiry_of(&self, account: &Address, role: Role) -> Option<u64> {
+        self.role_expiry.get(&(*account, role)).flatten()
+    }
+
+    /// Stored roles minus the ones whose grant has lapsed.
+    fn active_mask(&self, account: &Address) -> u8 {
+        let mask = self.roles.get(account).unwrap_or(0);
+        let current_time = self.env().get_block_time();
+        Role::ALL
+            .iter()
+            .copied()
+            .filter(|role| mask & role.flag() != 0)
+            .filter(|role| match self.expiry_of(account, *role) {
+                Some(valid_until) => current_time < valid_until,
+                None => true,
+            })
+            .fold(0, |acc, role| acc | role.flag())
+    }
+
+    fn check_valid_until(&self, valid_until: Option<u64>) {
+        if let Some(valid_until) = valid_until {
+            if valid_until <= self.env().get_block_time() {
+                self.env().revert(Error::InvalidInput);
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::string::String;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+use crate::validation;
+use crate::Error;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct AiAssessmentSubmitted {
+    pub assessment_id: u32,
+    pub oracle: Address,
+    pub subject_hash: String,
+    pub confidence: u8,
+    pub assessment_hash: String,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+/// Outcome suggested by the AI assessment
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum AiRecommendation {
+    Approve = 0,
+    Review = 1,
+    Reject = 2,
+}
+
+/// Which model assessed a credential, and a hash of its full assessment
+/// document (confidence, risk factors, justification)
+#[odra::odra_type]
+pub struct AiProvenance {
+    pub model_id: String,
+    pub model_version: String,
+    /// Hex-encoded 32-byte hash of the assessment document.
+    pub assessment_hash: String,
+    pub recommendation: AiRecommendation,
+}
+
+impl AiProvenance {
+    /// True when the fields are present, bounded and the hash is 32 bytes of hex.
+    pub fn is_well_formed(&self) -> bool {
+        !self.model_id.is_empty()
+            && self.model_id.len() <= 64
+            && !self.model_version.is_empty()
+            && self.model_version.len() <= 32
+            && is_hash(&self.assessment_hash)
+    }
+
+    /// Anything but an APPROVE recommendation needs a human reviewer.
+    pub fn needs_review(&self) -> bool {
+        self.recommendation != AiRecommendation::Approve
+    }
+}
+
+/// Confidence score recorded on-chain by an AI oracle for one credential hash
+#[odra::odra_type]
+pub struct AiAssessment {
+    pub assessment_id: u32,
+    pub oracle: Address,
+    /// Credential hash the assessment is about.
+    pub subject_hash: String,
+    pub confidence: u8,
+    /// Hex-encoded 32-byte hash of the full assessment document.
+    pub assessment_hash: String,
+    pub submitted_at: u64,
+    /// Credential issued on the strength of this assessment; each is single-use.
+    pub used_for: Option<U256>,
+}
+
+// ================ MODULE ================
+
+/// Assessments posted by AI oracles, consumed once by issuance
+#[odra::module]
+pub struct AiAssessments {
+    assessments: Mapping<u32, AiAssessment>,
+    assessment_counter: Var<u32>,
+}
+
+#[odra::module]
+impl AiAssessments {
+    pub fn submit(
+        &mut self,
+        oracle: Address,
+        subject_hash: String,
+        confidence: u8,
+        assessment_hash: String,
+    ) -> u32 {
+        if confidence > 100 || !is_hash(&subject_hash) || !is_hash(&assessment_hash) {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        let assessment_id = self.assessment_counter.get().unwrap_or(0);
+        self.assessment_counter.set(assessment_id + 1);
+
+        let current_time = self.env().get_block_time();
+        self.assessments.set(&assessment_id, AiAssessment {
+            assessment_id,
+            oracle,
+            subject_hash: subject_hash.clone(),
+            confidence,
+            assessment_hash: assessment_hash.clone(),
+            submitted_at: current_time,
+            used_for: None,
+        });
+
+        self.env().emit_event(AiAssessmentSubmitted {
+            assessment_id,
+            oracle,
+            subject_hash,
+            confidence,
+            assessment_hash,
+            timestamp: current_time,
+        });
+
+        assessment_id
+    }
+
+    pub fn get(&self, assessment_id: u32) -> Option<AiAssessment> {
+        self.assessments.get(&assessment_id)
+    }
+
+    /// Looks up an assessment that has not backed a credential yet.
+    pub fn unused_or_revert(&self, assessment_id: u32) -> AiAssessment {
+        let assessment = match self.get(assessment_id) {
+            Some(a) => a,
+            None => self.env().revert(Error::AssessmentNotFound),
+        };
+
+        if assessment.used_for.is_some() {
+            self.env().revert(Error::AssessmentAlreadyUsed);
+        }
+
+        assessment
+    }
+
+    pub fn mark_used(&mut self, assessment_id: u32, credential_id: U256) {
+        let mut assessment = self.unused_or_revert(assessment_id);
+        assessment.used_for = Some(credential_id);
+        self.assessments.set(&assessment_id, assessment);
+    }
+}
+
+fn is_hash(value: &str) -> bool {
+    validation::parse_hex_hash(value).is_ok()
+}
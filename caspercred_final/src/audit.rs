@@ -0,0 +1,38 @@
+use alloc::string::String;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// Audit Log Entry
+#[odra::odra_type]
+pub struct AuditLog {
+    pub action: String,
+    pub actor: Address,
+    pub timestamp: u64,
+    pub details: String,
+}
+
+/// Per-credential audit history: (credential id, index) -> entry, plus counts
+#[odra::module]
+pub struct AuditTrail {
+    logs: Mapping<(U256, u32), AuditLog>,
+    count: Mapping<U256, u32>,
+}
+
+#[odra::module]
+impl AuditTrail {
+    /// Appends an entry and returns the new entry count for the credential.
+    pub fn append(&mut self, credential_id: U256, log: AuditLog) -> u32 {
+        let count = self.count(credential_id);
+        self.logs.set(&(credential_id, count), log);
+        self.count.set(&credential_id, count + 1);
+        count + 1
+    }
+
+    pub fn count(&self, credential_id: U256) -> u32 {
+        self.count.get(&credential_id).unwrap_or(0)
+    }
+
+    pub fn get(&self, credential_id: U256, index: u32) -> Option<AuditLog> {
+        self.logs.get(&(credential_id, index))
+    }
+}
@@ -0,0 +1,45 @@
+//! Small key -> value claims stored on a credential, so that other contracts
+//! can check attributes such as `role` without fetching the IPFS document.
+
+use alloc::string::String;
+
+/// Most claims on one credential
+pub const MAX_CLAIMS: usize = 8;
+
+/// Longest claim key, in bytes
+pub const MAX_CLAIM_KEY_LEN: usize = 32;
+
+/// Longest claim value, in bytes
+pub const MAX_CLAIM_VALUE_LEN: usize = 64;
+
+#[odra::odra_type]
+pub struct Claim {
+    /// Lowercase ASCII letters, digits, `_`, `-` and `.`.
+    pub key: String,
+    pub value: String,
+}
+
+/// True when there are at most `MAX_CLAIMS` claims, the keys are well formed
+/// and unique, and no value is empty or longer than `MAX_CLAIM_VALUE_LEN`.
+pub fn are_well_formed(claims: &[Claim]) -> bool {
+    claims.len() <= MAX_CLAIMS
+        && claims.iter().enumerate().all(|(i, claim)| {
+            is_claim_key(&claim.key)
+                && !claim.value.is_empty()
+                && claim.value.len() <= MAX_CLAIM_VALUE_LEN
+                && claims[..i].iter().all(|other| other.key != claim.key)
+        })
+}
+
+/// Value of the claim with `key`, if any.
+pub fn value_of<'a>(claims: &'a [Claim], key: &str) -> Option<&'a str> {
+    claims.iter().find(|claim| claim.key == key).map(|claim| claim.value.as_str())
+}
+
+fn is_claim_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_CLAIM_KEY_LEN
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'-' | b'.'))
+}
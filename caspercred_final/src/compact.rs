@@ -0,0 +1,53 @@
+//! Fixed-size binary forms of credential hashes and signatures, with
+//! conversions from and to the hex strings taken by the entry points.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::bytesrepr::Bytes;
+
+use crate::encoding;
+
+/// Length of an Ed25519 or secp256k1 signature without its tag
+const SIGNATURE_LENGTH: usize = 64;
+
+/// Casper signature algorithm, numbered like the tag byte of `crypto::Signature`
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum SignatureAlgorithm {
+    Ed25519 = 1,
+    Secp256k1 = 2,
+}
+
+/// Algorithm-tagged signature stored as 65 bytes instead of 130+ hex characters
+#[odra::odra_type]
+pub struct SignatureBytes {
+    pub algorithm: SignatureAlgorithm,
+    pub signature: [u8; SIGNATURE_LENGTH],
+}
+
+impl SignatureBytes {
+    /// Parses a hex-encoded, algorithm-tagged Casper signature.
+    pub fn from_hex(input: &str) -> Option<Self> {
+        let tagged = encoding::decode_signature(input)?;
+        let algorithm = match tagged.first()? {
+            1 => SignatureAlgorithm::Ed25519,
+            2 => SignatureAlgorithm::Secp256k1,
+            _ => return None,
+        };
+        let signature = tagged.get(1..)?.try_into().ok()?;
+        Some(SignatureBytes { algorithm, signature })
+    }
+
+    /// Tag byte followed by the signature, as `verify_signature` expects.
+    pub fn to_tagged_bytes(&self) -> Bytes {
+        let mut tagged = Vec::with_capacity(1 + SIGNATURE_LENGTH);
+        tagged.push(self.algorithm as u8);
+        tagged.extend_from_slice(&self.signature);
+        Bytes::from(tagged)
+    }
+
+    /// The hex form accepted by `from_hex`.
+    pub fn to_hex(&self) -> String {
+        encoding::encode_hex(&self.to_tagged_bytes())
+    }
+}
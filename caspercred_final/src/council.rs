@@ -0,0 +1,258 @@
+use alloc::vec::Vec;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+use crate::access_control::Role;
+use crate::Error;
+
+/// How long a council proposal stays open (7 days)
+const PROPOSAL_TTL: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Upper bound on council size (keeps reconfiguration loops bounded)
+const MAX_MEMBERS: u32 = 20;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct CouncilConfigured {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+    pub configured_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CouncilActionProposed {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CouncilActionApproved {
+    pub proposal_id: u32,
+    pub approver: Address,
+    pub approvals: u32,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CouncilActionExecuted {
+    pub proposal_id: u32,
+    pub executor: Address,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+/// Privileged operations that require council approval once a council is set
+#[odra::odra_type]
+pub enum CouncilAction {
+    Pause,
+    Unpause,
+    TransferOwnership { new_owner: Address },
+    CancelOwnershipTransfer,
+    SetAccessLevel { user: Address, level: u8, valid_until: Option<u64> },
+    UpdateCouncil { members: Vec<Address>, threshold: u32 },
+    GrantRole { account: Address, role: Role, valid_until: Option<u64> },
+    RevokeRole { account: Address, role: Role },
+    SetRequiredEndorsements { credential_id: U256, required: u32 },
+}
+
+#[odra::odra_type]
+pub struct CouncilProposal {
+    pub action: CouncilAction,
+    pub proposer: Address,
+    pub approvals: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub executed: bool,
+    pub council_epoch: u32,
+}
+
+// ================ MODULE ================
+
+/// M-of-N admin council. A threshold of 0 means no council is configured.
+/// Reconfiguring bumps the epoch, which voids proposals made under the old council.
+#[odra::module]
+pub struct AdminCouncil {
+    members: Mapping<Address, bool>,
+    member_at: Mapping<u32, Address>,
+    member_count: Var<u32>,
+    threshold: Var<u32>,
+    epoch: Var<u32>,
+    proposals: Mapping<u32, CouncilProposal>,
+    approvals: Mapping<(u32, Address), bool>,
+    proposal_counter: Var<u32>,
+}
+
+#[odra::module]
+impl AdminCouncil {
+    pub fn is_active(&self) -> bool {
+        self.get_threshold() > 0
+    }
+
+    pub fn is_member(&self, address: &Address) -> bool {
+        self.members.get(address).unwrap_or(false)
+    }
+
+    pub fn get_threshold(&self) -> u32 {
+        self.threshold.get().unwrap_or(0)
+    }
+
+    pub fn get_members(&self) -> Vec<Address> {
+        (0..self.member_count.get().unwrap_or(0))
+            .filter_map(|i| self.member_at.get(&i))
+            .collect()
+    }
+
+    pub fn get_proposal(&self, proposal_id: u32) -> Option<CouncilProposal> {
+        self.proposals.get(&proposal_id)
+    }
+
+    pub fn has_approved(&self, proposal_id: u32, member: &Address) -> bool {
+        self.approvals.get(&(proposal_id, *member)).unwrap_or(false)
+    }
+
+    /// Checks a council shape: `threshold` of 0 (with no members) disables the council.
+    fn validate_config(&self, members: &[Address], threshold: u32) {
+        let count = members.len() as u32;
+
+        if count > MAX_MEMBERS || threshold > count || (threshold == 0 && count > 0) {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            if members[..i].contains(member) {
+                self.env().revert(Error::InvalidInput);
+            }
+        }
+    }
+
+    /// Replaces the member set and threshold.
+    pub fn configure(&mut self, members: Vec<Address>, threshold: u32, configured_by: Address) {
+        self.validate_config(&members, threshold);
+
+        for old_member in self.get_members() {
+            self.members.set(&old_member, false);
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            self.members.set(member, true);
+            self.member_at.set(&(i as u32), *member);
+        }
+        self.member_count.set(members.len() as u32);
+        self.threshold.set(threshold);
+        self.epoch.set(self.epoch.get().unwrap_or(0) + 1);
+
+        self.env().emit_event(CouncilConfigured {
+            members,
+            threshold,
+            configured_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Opens a proposal; the proposer's approval is counted straight away.
+    pub fn propose(&mut self, proposer: Address, action: CouncilAction) -> u32 {
+        if !self.is_member(&proposer) {
+            self.env().revert(Error::NotAuthorized);
+        }
+
+        let current_time = self.env().get_block_time();
+        let proposal_id = self.proposal_counter.get().unwrap_or(0);
+        self.proposal_counter.set(proposal_id + 1);
+
+        let expires_at = current_time + PROPOSAL_TTL;
+
+        self.proposals.set(&proposal_id, CouncilProposal {
+            action: action.clone(),
+            proposer,
+            approvals: 0,
+            created_at: current_time,
+            expires_at,
+            executed: false,
+            council_epoch: self.epoch.get().unwrap_or(0),
+        });
+
+        self.env().emit_event(CouncilActionProposed {
+            proposal_id,
+            proposer,
+            expires_at,
+            timestamp: current_time,
+        });
+
+        self.approve(proposer, proposal_id);
+        proposal_id
+    }
+
+    pub fn approve(&mut self, member: Address, proposal_id: u32) {
+        if !self.is_member(&member) {
+            self.env().revert(Error::NotAuthorized);
+        }
+
+        let mut proposal = self.open_proposal(proposal_id);
+
+        if self.has_approved(proposal_id, &member) {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        proposal.approvals += 1;
+        let approvals = proposal.approvals;
+        self.approvals.set(&(proposal_id, member), true);
+        self.proposals.set(&proposal_id, proposal);
+
+        self.env().emit_event(CouncilActionApproved {
+            proposal_id,
+            approver: member,
+            approvals,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Marks an approved proposal as executed and hands back its action.
+    pub fn take_for_execution(&mut self, executor: Address, proposal_id: u32) -> CouncilAction {
+        if !self.is_member(&executor) {
+            self.env().revert(Error::NotAuthorized);
+        }
+
+        let mut proposal = self.open_proposal(proposal_id);
+
+        if proposal.approvals < self.get_threshold() {
+            self.env().revert(Error::ThresholdNotMet);
+        }
+
+        proposal.executed = true;
+        let action = proposal.action.clone();
+        self.proposals.set(&proposal_id, proposal);
+
+        self.env().emit_event(CouncilActionExecuted {
+            proposal_id,
+            executor,
+            timestamp: self.env().get_block_time(),
+        });
+
+        action
+    }
+
+    fn open_proposal(&self, proposal_id: u32) -> CouncilProposal {
+        let proposal = match self.proposals.get(&proposal_id) {
+            Some(p) => p,
+            None => self.env().revert(Error::ProposalNotFound),
+        };
+
+        if proposal.executed {
+            self.env().revert(Error::ProposalAlreadyExecuted);
+        }
+
+        if proposal.council_epoch != self.epoch.get().unwrap_or(0)
+            || self.env().get_block_time() >= proposal.expires_at
+        {
+            self.env().revert(Error::ProposalExpired);
+        }
+
+        proposal
+    }
+}
+
@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+use crate::status_list::StatusList;
+
+/// Holder and issuer lookups: (address, index) -> credential id, plus counts,
+/// the credential hash -> id uniqueness index, and the revocation status list.
+/// Credentials waiting for acceptance or review are kept in a separate
+/// per-holder pending list until they become active or are rejected.
+#[odra::module]
+pub struct CredentialIndex {
+    holder_credentials: Mapping<(Address, u32), U256>,
+    issuer_credentials: Mapping<(Address, u32), U256>,
+    holder_count: Mapping<Address, u32>,
+    issuer_count: Mapping<Address, u32>,
+    holder_pending: Mapping<(Address, u32), U256>,
+    holder_pending_count: Mapping<Address, u32>,
+    holder_rotation_nonce: Mapping<Address, u64>,
+    hash_to_id: Mapping<[u8; 32], U256>,
+    status_list: SubModule<StatusList>,
+}
+
+#[odra::module]
+impl CredentialIndex {
+    pub fn add_holder_credential(&mut self, holder: Address, credential_id: U256) {
+        let idx = self.holder_count(holder);
+        self.holder_credentials.set(&(holder, idx), credential_id);
+        self.holder_count.set(&holder, idx + 1);
+    }
+
+    pub fn add_issuer_credential(&mut self, issuer: Address, credential_id: U256) {
+        let idx = self.issuer_count(issuer);
+        self.issuer_credentials.set(&(issuer, idx), credential_id);
+        self.issuer_count.set(&issuer, idx + 1);
+    }
+
+    pub fn holder_count(&self, holder: Address) -> u32 {
+        self.holder_count.get(&holder).unwrap_or(0)
+    }
+
+    pub fn holder_credential_at(&self, holder: Address, index: u32) -> Option<U256> {
+        if index >= self.holder_count(holder) {
+            return None;
+        }
+        self.holder_credentials.get(&(holder, index))
+    }
+
+    /// Credential ids indexed under `holder`, in index order.
+    pub fn holder_credentials(&self, holder: Address) -> Vec<U256> {
+        (0..self.holder_count(holder))
+            .filter_map(|index| self.holder_credentials.get(&(holder, index)))
+            .collect()
+    }
+
+    /// Replaces `holder`'s list; entries past its new length become unreachable.
+    pub fn set_holder_credentials(&mut self, holder: Address, credential_ids: Vec<U256>) {
+        for (index, credential_id) in credential_ids.iter().enumerate() {
+            self.holder_credentials.set(&(holder, index as u32), *credential_id);
+        }
+        self.holder_count.set(&holder, credential_ids.len() as u32);
+    }
+
+    pub fn add_holder_pending(&mut self, holder: Address, credential_id: U256) {
+        let idx = self.holder_pending_count(holder);
+        self.holder_pending.set(&(holder, idx), credential_id);
+        self.holder_pending_count.set(&holder, idx + 1);
+    }
+
+    pub fn holder_pending_count(&self, holder: Address) -> u32 {
+        self.holder_pending_count.get(&holder).unwrap_or(0)
+    }
+
+    /// Pending credential ids of `holder`; the order is not preserved by
+    /// `remove_holder_pending`.
+    pub fn holder_pending(&self, holder: Address) -> Vec<U256> {
+        (0..self.holder_pending_count(holder))
+            .filter_map(|index| self.holder_pending.get(&(holder, index)))
+            .collect()
+    }
+
+    /// Replaces `holder`'s pending list, like `set_holder_credentials`.
+    pub fn set_holder_pending(&mut self, holder: Address, credential_ids: Vec<U256>) {
+        for (index, credential_id) in credential_ids.iter().enumerate() {
+            self.holder_pending.set(&(holder, index as u32), *credential_id);
+        }
+        self.holder_pending_count.set(&holder, credential_ids.len() as u32);
+    }
+
+    /// Drops `credential_id` from `holder`'s pending list (swap-remove).
+    pub fn remove_holder_pending(&mut self, holder: Address, credential_id: U256) {
+        let count = self.holder_pending_count(holder);
+        let position = (0..count).find(|index| self.holder_pending.get(&(holder, *index)) == Some(credential_id));
+        if let Some(position) = position {
+            let last = count - 1;
+            if position != last {
+                if let Some(moved) = self.holder_pending.get(&(holder, last)) {
+                    self.holder_pending.set(&(holder, position), moved);
+                }
+            }
+            self.holder_pending_count.set(&holder, last);
+        }
+    }
+
+    /// Nonce the next self-service holder rotation of `holder` must sign.
+    pub fn holder_rotation_nonce(&self, holder: Address) -> u64 {
+        self.holder_rotation_nonce.get(&holder).unwrap_or(0)
+    }
+
+    pub fn bump_holder_rotation_nonce(&mut self, holder: Address) {
+        let nonce = self.holder_rotation_nonce(holder);
+        self.holder_rotation_nonce.set(&holder, nonce + 1);
+    }
+
+    pub fn issuer_count(&self, issuer: Address) -> u32 {
+        self.issuer_count.get(&issuer).unwrap_or(0)
+    }
+
+    pub fn issuer_credential_at(&self, issuer: Address, index: u32) -> Option<U256> {
+        self.issuer_credentials.get(&(issuer, index))
+    }
+
+    pub fn add_credential_hash(&mut self, credential_hash: [u8; 32], credential_id: U256) {
+        self.hash_to_id.set(&credential_hash, credential_id);
+    }
+
+    pub fn credential_by_hash(&self, credential_hash: [u8; 32]) -> Option<U256> {
+        self.hash_to_id.get(&credential_hash)
+    }
+
+    pub fn assign_status_index(&mut self) -> u32 {
+        self.status_list.assign()
+    }
+
+    pub fn mark_status_revoked(&mut self, status_index: u32) {
+        self.status_list.set_revoked(status_index);
+    }
+
+    pub fn status_list_length(&self) -> u32 {
+        self.status_list.length()
+    }
+
+    pub fn status_list_chunk(&self, offset: u32, max_bytes: u32) -> Bytes {
+        self.status_list.chunk(offset, max_bytes)
+    }
+}
@@ -0,0 +1,343 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::PublicKey;
+use odra::prelude::*;
+
+use crate::encoding;
+use crate::validation;
+use crate::Error;
+
+/// DID method resolved by this registry
+pub const CASPER_METHOD: &str = "casper";
+
+/// Upper bound on verification methods (current and rotated-out) per document;
+/// past it, each rotation drops the oldest rotated-out method
+const MAX_VERIFICATION_METHODS: usize = 8;
+
+/// Upper bound on service endpoints per document
+const MAX_SERVICES: usize = 8;
+
+/// Upper bound on allowed external DID methods
+const MAX_EXTERNAL_METHODS: usize = 16;
+
+/// Longest method-specific identifier accepted for a did:casper DID
+const MAX_IDENTIFIER_LEN: usize = 64;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct DidCreated {
+    pub did: String,
+    pub controller: Address,
+    pub public_key: PublicKey,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct DidKeyRotated {
+    pub did: String,
+    pub key_id: String,
+    pub public_key: PublicKey,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct DidServiceUpdated {
+    pub did: String,
+    pub service_id: String,
+    pub removed: bool,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct DidDeactivated {
+    pub did: String,
+    pub deactivated_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct ExternalDidMethodChanged {
+    pub method: String,
+    pub allowed: bool,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+/// Key listed in a DID document; rotated-out keys stay listed as inactive
+#[odra::odra_type]
+pub struct VerificationMethod {
+    /// `<did>#key-<n>`
+    pub id: String,
+    pub public_key: PublicKey,
+    pub active: bool,
+    pub added_at: u64,
+}
+
+#[odra::odra_type]
+pub struct ServiceEndpoint {
+    pub id: String,
+    pub service_type: String,
+    pub endpoint: String,
+}
+
+/// did:casper DID document, bound to the controlling account
+#[odra::odra_type]
+pub struct DidDocument {
+    pub id: String,
+    pub controller: Address,
+    pub verification_methods: Vec<VerificationMethod>,
+    pub services: Vec<ServiceEndpoint>,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// Final: a deactivated DID no longer resolves as active.
+    pub deactivated: bool,
+}
+
+// ================ MODULE ================
+
+/// did:casper documents (did -> document) and the external DID methods
+/// accepted alongside them
+#[odra::module]
+pub struct DidRegistry {
+    documents: Mapping<String, DidDocument>,
+    external_methods: Var<Vec<String>>,
+}
+
+#[odra::module]
+impl DidRegistry {
+    pub fn create(&mut self, did: String, controller: Address, public_key: PublicKey) {
+        if !is_casper_did(&did) {
+            self.env().revert(Error::InvalidDID);
+        }
+
+        if self.documents.get(&did).is_some() {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        let current_time = self.env().get_block_time();
+        self.documents.set(&did, DidDocument {
+            id: did.clone(),
+            controller,
+            verification_methods: vec![VerificationMethod {
+                id: format!("{}#key-1", did),
+                public_key: public_key.clone(),
+                active: true,
+                added_at: current_time,
+            }],
+            services: Vec::new(),
+            created_at: current_time,
+            updated_at: current_time,
+            deactivated: false,
+        });
+
+        self.env().emit_event(DidCreated {
+            did,
+            controller,
+            public_key,
+            timestamp: current_time,
+        });
+    }
+
+    /// Deactivates the current keys and adds `public_key` as the only active
+    /// one. Key numbers keep counting up when old methods are dropped.
+    pub fn rotate_key(&mut self, did: String, public_key: PublicKey) {
+        let mut document = self.active_or_revert(&did);
+
+        if document.verification_methods.iter().any(|method| method.public_key == public_key) {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        let current_time = self.env().get_block_time();
+        let key_number = document.verification_methods.last().map_or(0, key_number) + 1;
+        let key_id = format!("{}#key-{}", did, key_number);
+        for method in document.verification_methods.iter_mut() {
+            method.active = false;
+        }
+        if document.verification_methods.len() >= MAX_VERIFICATION_METHODS {
+            document.verification_methods.remove(0);
+        }
+        document.verification_methods.push(VerificationMethod {
+            id: key_id.clone(),
+            public_key: public_key.clone(),
+            active: true,
+            added_at: current_time,
+        });
+        document.updated_at = current_time;
+        self.documents.set(&did, document);
+
+        self.env().emit_event(DidKeyRotated {
+            did,
+            key_id,
+            public_key,
+            timestamp: current_time,
+        });
+    }
+
+    /// Adds a service endpoint, replacing any with the same id.
+    pub fn set_service(&mut self, did: String, service: ServiceEndpoint) {
+        let mut document = self.active_or_revert(&did);
+
+        if service.id.is_empty() || service.service_type.is_empty() || service.endpoint.is_empty() {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        let service_id = service.id.clone();
+        match document.services.iter().position(|s| s.id == service.id) {
+            Some(i) => document.services[i] = service,
+            None if document.services.len() < MAX_SERVICES => document.services.push(service),
+            None => self.env().revert(Error::InvalidInput),
+        }
+        self.save_service_change(did, document, service_id, false);
+    }
+
+    pub fn remove_service(&mut self, did: String, service_id: String) {
+        let mut document = self.active_or_revert(&did);
+
+        match document.services.iter().position(|s| s.id == service_id) {
+            Some(i) => document.services.remove(i),
+            None => self.env().revert(Error::InvalidInput),
+        };
+        self.save_service_change(did, document, service_id, true);
+    }
+
+    pub fn deactivate(&mut self, did: String, deactivated_by: Address) {
+        let mut document = self.active_or_revert(&did);
+
+        let current_time = self.env().get_block_time();
+        document.deactivated = true;
+        document.updated_at = current_time;
+        self.documents.set(&did, document);
+
+        self.env().emit_event(DidDeactivated {
+            did,
+            deactivated_by,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn resolve(&self, did: String) -> Option<DidDocument> {
+        self.documents.get(&did)
+    }
+
+    pub fn external_methods(&self) -> Vec<String> {
+        self.external_methods.get().unwrap_or_default()
+    }
+
+    /// Allows or disallows DIDs of another method (e.g. "web", "key") in credentials.
+    pub fn set_external_method(&mut self, method: String, allowed: bool, changed_by: Address) {
+        let valid = !method.is_empty()
+            && method != CASPER_METHOD
+            && method.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit());
+        if !valid {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        let mut methods = self.external_methods();
+        let position = methods.iter().position(|m| *m == method);
+        match (position, allowed) {
+            (None, true) if methods.len() < MAX_EXTERNAL_METHODS => methods.push(method.clone()),
+            (Some(i), false) => {
+                methods.remove(i);
+            }
+            (None, true) => self.env().revert(Error::InvalidInput),
+            _ => {}
+        }
+        self.external_methods.set(methods);
+
+        self.env().emit_event(ExternalDidMethodChanged {
+            method,
+            allowed,
+            changed_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+}
+
+// Lookups taking `&str` (kept out of the module impl, whose methods must
+// take entry-point compatible arguments)
+impl DidRegistry {
+    /// Document of an existing, not deactivated DID; reverts with `InvalidDID` otherwise.
+    pub fn active_or_revert(&self, did: &str) -> DidDocument {
+        match self.documents.get(&did.to_string()) {
+            Some(document) if !document.deactivated => document,
+            _ => self.env().revert(Error::InvalidDID),
+        }
+    }
+
+    /// True for did:casper DIDs with an active document, and for DIDs of an
+    /// allowed external method.
+    pub fn accepts(&self, did: &str) -> bool {
+        match validation::parse_did(did) {
+            Ok(parsed) if parsed.method == CASPER_METHOD => {
+                self.documents.get(&did.to_string()).is_some_and(|document| !document.deactivated)
+            }
+            Ok(parsed) => self.external_methods().iter().any(|m| m == parsed.method),
+            Err(_) => false,
+        }
+    }
+
+    /// False when `did` is a did:casper DID whose document is missing,
+    /// deactivated or controlled by an account other than `holder`. External
+    /// DIDs cannot be checked on-chain.
+    pub fn is_bound_to(&self, did: &str, holder: &Address) -> bool {
+        match validation::parse_did(did) {
+            Ok(parsed) if parsed.method == CASPER_METHOD => self
+                .documents
+                .get(&did.to_string())
+                .is_some_and(|document| !document.deactivated && document.controller == *holder),
+            _ => true,
+        }
+    }
+
+    /// Active verification keys of `did`'s document, if it is active and
+    /// controlled by `controller`.
+    pub fn active_keys_of(&self, did: &str, controller: &Address) -> Vec<PublicKey> {
+        match self.documents.get(&did.to_string()) {
+            Some(document) if !document.deactivated && document.controller == *controller => document
+                .verification_methods
+                .into_iter()
+                .filter(|method| method.active)
+                .map(|method| method.public_key)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn save_service_change(&mut self, did: String, mut document: DidDocument, service_id: String, removed: bool) {
+        let current_time = self.env().get_block_time();
+        document.updated_at = current_time;
+        self.documents.set(&did, document);
+
+        self.env().emit_event(DidServiceUpdated {
+            did,
+            service_id,
+            removed,
+            timestamp: current_time,
+        });
+    }
+}
+
+/// did:casper DID derived from `address` (the hex of its account or package
+/// hash), which only that account can create.
+pub fn account_did(address: &Address) -> String {
+    format!("did:{}:{}", CASPER_METHOD, encoding::encode_hex(&address.value()))
+}
+
+/// `n` of a `<did>#key-<n>` method id.
+fn key_number(method: &VerificationMethod) -> u32 {
+    method
+        .id
+        .rsplit_once("#key-")
+        .and_then(|(_, number)| number.parse().ok())
+        .unwrap_or(0)
+}
+
+/// A syntactically valid did:casper DID with an identifier of at most 64 characters.
+fn is_casper_did(did: &str) -> bool {
+    validation::parse_did(did)
+        .is_ok_and(|parsed| parsed.method == CASPER_METHOD && parsed.identifier.len() <= MAX_IDENTIFIER_LEN)
+}
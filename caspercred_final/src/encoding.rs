@@ -0,0 +1,50 @@
+//! Hex helpers shared by the contract and host-side tooling.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::bytesrepr::{Bytes, FromBytes};
+use odra::casper_types::crypto::Signature;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Decodes a hex string (either case). Returns `None` on odd length or non-hex input.
+pub fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+
+    bytes
+        .chunks(2)
+        .map(|pair| Some((hex_value(pair[0])? << 4) | hex_value(pair[1])?))
+        .collect()
+}
+
+/// Encodes bytes as lowercase hex.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        out.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Decodes a hex-encoded, algorithm-tagged Casper signature. Malformed
+/// signatures are rejected here; the host would revert with its own error.
+pub fn decode_signature(input: &str) -> Option<Bytes> {
+    let bytes = decode_hex(input)?;
+    match Signature::from_bytes(&bytes) {
+        Ok((_, [])) => Some(Bytes::from(bytes)),
+        _ => None,
+    }
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
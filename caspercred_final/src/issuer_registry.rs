@@ -0,0 +1,321 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::PublicKey;
+use odra::prelude::*;
+
+use crate::did_registry::{DidRegistry, CASPER_METHOD};
+use crate::validation;
+use crate::Error;
+
+/// Upper bound on key slots per DID (keeps verification loops bounded);
+/// a revoked key's slot is reused by the next key added
+const MAX_KEYS_PER_DID: u32 = 8;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct IssuerRegistered {
+    pub did: String,
+    pub controller: Address,
+    pub name: String,
+    pub registered_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct IssuerUpdated {
+    pub did: String,
+    pub name: String,
+    pub metadata_ipfs_hash: String,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct IssuerStatusChanged {
+    pub did: String,
+    pub old_status: IssuerStatus,
+    pub new_status: IssuerStatus,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct IssuerKeyAdded {
+    pub did: String,
+    pub key_index: u32,
+    pub public_key: PublicKey,
+    pub added_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct IssuerKeyRevoked {
+    pub did: String,
+    pub key_index: u32,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum IssuerStatus {
+    Active = 0,
+    Suspended = 1,
+    /// Final: a retired DID cannot be reactivated.
+    Retired = 2,
+}
+
+/// Issuer DID bound to the address allowed to issue under it
+#[odra::odra_type]
+pub struct IssuerRecord {
+    pub did: String,
+    pub controller: Address,
+    pub name: String,
+    pub metadata_ipfs_hash: String,
+    pub status: IssuerStatus,
+    pub registered_at: u64,
+    pub updated_at: u64,
+}
+
+/// Ed25519 or secp256k1 key allowed to sign credentials for a DID
+#[odra::odra_type]
+pub struct IssuerKey {
+    pub public_key: PublicKey,
+    pub active: bool,
+    pub added_at: u64,
+}
+
+// ================ MODULE ================
+
+/// Registry of issuer DIDs (did -> record) and their signing keys, plus the
+/// did:casper documents those DIDs resolve to
+#[odra::module]
+pub struct IssuerRegistry {
+    issuers: Mapping<String, IssuerRecord>,
+    keys: Mapping<(String, u32), IssuerKey>,
+    key_count: Mapping<String, u32>,
+    dids: SubModule<DidRegistry>,
+}
+
+#[odra::module]
+impl IssuerRegistry {
+    pub fn register(
+        &mut self,
+        did: String,
+        controller: Address,
+        name: String,
+        metadata_ipfs_hash: String,
+        registered_by: Address,
+    ) {
+        if let Err(error) = validation::parse_did(&did) {
+            self.env().revert(Error::from(error));
+        }
+
+        if self.issuers.get(&did).is_some() {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        // A did:casper document created first must belong to the same account
+        if self.dids.resolve(did.clone()).is_some_and(|document| document.controller != controller) {
+            self.env().revert(Error::InvalidDID);
+        }
+
+        self.check_metadata(&name, &metadata_ipfs_hash);
+
+        let current_time = self.env().get_block_time();
+        self.issuers.set(&did, IssuerRecord {
+            did: did.clone(),
+            controller,
+            name: name.clone(),
+            metadata_ipfs_hash,
+            status: IssuerStatus::Active,
+            registered_at: current_time,
+            updated_at: current_time,
+        });
+
+        self.env().emit_event(IssuerRegistered {
+            did,
+            controller,
+            name,
+            registered_by,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn update_metadata(
+        &mut self,
+        did: String,
+        name: String,
+        metadata_ipfs_hash: String,
+        updated_by: Address,
+    ) {
+        let mut record = self.get_or_revert(&did);
+        self.check_metadata(&name, &metadata_ipfs_hash);
+
+        let current_time = self.env().get_block_time();
+        record.name = name.clone();
+        record.metadata_ipfs_hash = metadata_ipfs_hash.clone();
+        record.updated_at = current_time;
+        self.issuers.set(&did, record);
+
+        self.env().emit_event(IssuerUpdated {
+            did,
+            name,
+            metadata_ipfs_hash,
+            updated_by,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn set_status(&mut self, did: String, status: IssuerStatus, changed_by: Address) {
+        let mut record = self.get_or_revert(&did);
+
+        if record.status == IssuerStatus::Retired {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        let old_status = record.status;
+        let current_time = self.env().get_block_time();
+        record.status = status;
+        record.updated_at = current_time;
+        self.issuers.set(&did, record);
+
+        self.env().emit_event(IssuerStatusChanged {
+            did,
+            old_status,
+            new_status: status,
+            changed_by,
+            timestamp: current_time,
+        });
+    }
+
+    /// Stores `public_key` in the first revoked slot, or a new one while
+    /// fewer than `MAX_KEYS_PER_DID` exist, and returns the slot's index.
+    pub fn add_key(&mut self, did: String, public_key: PublicKey, added_by: Address) -> u32 {
+        self.get_or_revert(&did);
+
+        let keys = self.keys_of(&did);
+        if keys.iter().any(|key| key.active && key.public_key == public_key) {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        let key_count = keys.len() as u32;
+        let key_index = match keys.iter().position(|key| !key.active) {
+            Some(index) => index as u32,
+            None if key_count < MAX_KEYS_PER_DID => key_count,
+            None => self.env().revert(Error::InvalidInput),
+        };
+
+        let current_time = self.env().get_block_time();
+        self.keys.set(&(did.clone(), key_index), IssuerKey {
+            public_key: public_key.clone(),
+            active: true,
+            added_at: current_time,
+        });
+        if key_index == key_count {
+            self.key_count.set(&did, key_count + 1);
+        }
+
+        self.env().emit_event(IssuerKeyAdded {
+            did,
+            key_index,
+            public_key,
+            added_by,
+            timestamp: current_time,
+        });
+
+        key_index
+    }
+
+    pub fn revoke_key(&mut self, did: String, key_index: u32, revoked_by: Address) {
+        let mut key = match self.keys.get(&(did.clone(), key_index)) {
+            Some(k) => k,
+            None => self.env().revert(Error::InvalidInput),
+        };
+
+        key.active = false;
+        self.keys.set(&(did.clone(), key_index), key);
+
+        self.env().emit_event(IssuerKeyRevoked {
+            did,
+            key_index,
+            revoked_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+}
+
+// Lookups taking `&str` (kept out of the module impl, whose methods must
+// take entry-point compatible arguments)
+impl IssuerRegistry {
+    pub fn dids(&self) -> &DidRegistry {
+        &self.dids
+    }
+
+    pub fn dids_mut(&mut self) -> &mut DidRegistry {
+        &mut self.dids
+    }
+
+    pub fn get(&self, did: &str) -> Option<IssuerRecord> {
+        self.issuers.get(&did.to_string())
+    }
+
+    /// True when `did` is registered, active and controlled by `caller`.
+    pub fn is_active_controller(&self, did: &str, caller: &Address) -> bool {
+        match self.get(did) {
+            Some(record) => record.controller == *caller && record.status == IssuerStatus::Active,
+            None => false,
+        }
+    }
+
+    pub fn get_or_revert(&self, did: &str) -> IssuerRecord {
+        match self.get(did) {
+            Some(record) => record,
+            None => self.env().revert(Error::InvalidDID),
+        }
+    }
+
+    pub fn keys_of(&self, did: &str) -> Vec<IssuerKey> {
+        let did = did.to_string();
+        (0..self.key_count.get(&did).unwrap_or(0))
+            .filter_map(|i| self.keys.get(&(did.clone(), i)))
+            .collect()
+    }
+
+    pub fn active_keys(&self, did: &str) -> Vec<PublicKey> {
+        self.keys_of(did)
+            .into_iter()
+            .filter(|key| key.active)
+            .map(|key| key.public_key)
+            .collect()
+    }
+
+    /// Keys that may sign for `did`: its active registry keys, narrowed for a
+    /// did:casper DID to those also active in its document, which must be
+    /// held by the DID's controller.
+    pub fn signing_keys(&self, did: &str) -> Vec<PublicKey> {
+        let keys = self.active_keys(did);
+        if !validation::parse_did(did).is_ok_and(|parsed| parsed.method == CASPER_METHOD) {
+            return keys;
+        }
+
+        let document_keys = match self.get(did) {
+            Some(record) => self.dids.active_keys_of(did, &record.controller),
+            None => Vec::new(),
+        };
+        keys.into_iter().filter(|key| document_keys.contains(key)).collect()
+    }
+
+    fn check_metadata(&self, name: &str, metadata_ipfs_hash: &str) {
+        if name.is_empty() {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        if let Err(error) = validation::parse_cid(metadata_ipfs_hash) {
+            self.env().revert(Error::from(error));
+        }
+    }
+}
@@ -4,22 +4,113 @@ extern crate alloc;
 
 use alloc::string::String;
 use alloc::vec::Vec;
+use odra::args::Maybe;
 use odra::prelude::*;
-use odra::casper_types::U256;
+use odra::casper_types::bytesrepr::{Bytes, ToBytes};
+use odra::casper_types::{PublicKey, U256};
+
+pub mod access_control;
+pub mod ai_assessment;
+pub mod audit;
+pub mod claims;
+pub mod compact;
+pub mod council;
+pub mod credential_index;
+pub mod did_registry;
+pub mod encoding;
+pub mod issuer_registry;
+pub mod merkle;
+pub mod ownership;
+pub mod policy;
+pub mod rate_limit;
+pub mod request_queue;
+pub mod schema_registry;
+pub mod security;
+pub mod status_list;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod status_list_export;
+pub mod validation;
+
+use access_control::AccessControl;
+use ai_assessment::AiAssessments;
+pub use ai_assessment::{AiAssessment, AiProvenance, AiRecommendation};
+pub use access_control::{Role, RoleGrant};
+use audit::AuditTrail;
+pub use audit::AuditLog;
+pub use claims::Claim;
+pub use compact::{SignatureAlgorithm, SignatureBytes};
+use council::AdminCouncil;
+pub use council::{CouncilAction, CouncilProposal};
+use credential_index::CredentialIndex;
+pub use did_registry::{account_did, DidDocument, ServiceEndpoint, VerificationMethod};
+use issuer_registry::IssuerRegistry;
+pub use issuer_registry::{IssuerKey, IssuerRecord, IssuerStatus};
+use ownership::Ownership;
+use policy::CredentialPolicy;
+use rate_limit::RateLimiter;
+pub use rate_limit::RateLimitData;
+use request_queue::RequestQueue;
+pub use request_queue::{CredentialRequest, RequestStatus};
+use schema_registry::SchemaRegistry;
+pub use schema_registry::CredentialSchema;
+use security::SecurityMonitor;
+pub use security::VerificationData;
+
+/// Most credentials accepted by one `issue_credentials_batch` call
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// One day in block-time units (milliseconds)
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Most `superseded_by` links followed by one `get_latest_credential_id` call
+const MAX_SUPERSESSION_HOPS: u32 = 32;
+
+/// Most endorsements stored on (and required by) one credential
+pub const MAX_ENDORSEMENTS: u32 = 16;
+
+/// Domain prefix of the message signed for `rotate_own_holder_address`
+pub const HOLDER_ROTATION_DOMAIN: &[u8] = b"CASPERCRED_ROTATE_HOLDER";
+
+/// Optional entry-point arguments arrive as `Maybe`, which callers may leave
+/// out entirely (a plain `Option` argument must always be sent).
+fn into_option<T>(value: Maybe<T>) -> Option<T> {
+    match value {
+        Maybe::Some(value) => Some(value),
+        Maybe::None => None,
+    }
+}
 
 // ================ EVENTS (Audit Trail) ================
 
-#[odra::event]
-pub struct CredentialIssued {
-    pub credential_id: U256,
-    pub holder: Address,
-    pub issuer: Address,
-    pub issuer_did: String,
-    pub holder_did: String,
-    pub ai_confidence: u8,
-    pub credential_hash: String,
-    pub ipfs_hash: String,
-    pub timestamp: u64,
+pub use issued_event::CredentialIssued;
+
+/// Own module so that the lint allowance covers only the constructor
+/// `#[odra::event]` generates, which takes every field
+mod issued_event {
+    #![allow(clippy::too_many_arguments)]
+
+    use super::*;
+
+    #[odra::event]
+    pub struct CredentialIssued {
+        pub credential_id: U256,
+        pub holder: Address,
+        pub issuer: Address,
+        pub issuer_did: String,
+        pub holder_did: String,
+        pub ai_confidence: u8,
+        pub credential_hash: String,
+        pub ipfs_hash: String,
+        pub timestamp: u64,
+        pub schema_id: u32,
+        /// AI provenance, flattened; the strings are empty and the recommendation
+        /// `None` when the credential was issued without it.
+        pub ai_model_id: String,
+        pub ai_model_version: String,
+        pub ai_assessment_hash: String,
+        pub ai_recommendation: Option<AiRecommendation>,
+        pub ai_assessment_id: u32,
+    }
 }
 
 #[odra::event]
@@ -31,6 +122,71 @@ pub struct CredentialRevoked {
     pub was_already_revoked: bool,
 }
 
+#[odra::event]
+pub struct CredentialAccepted {
+    pub credential_id: U256,
+    pub holder: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialRejected {
+    pub credential_id: U256,
+    pub holder: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialReviewed {
+    pub credential_id: U256,
+    pub reviewer: Address,
+    pub approved: bool,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialSuperseded {
+    pub old_credential_id: U256,
+    pub new_credential_id: U256,
+    pub superseded_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialEndorsed {
+    pub credential_id: U256,
+    pub endorser: Address,
+    pub endorser_did: String,
+    pub endorsement_count: u32,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct EndorsementRequirementChanged {
+    pub credential_id: U256,
+    pub required: u32,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct HolderRotated {
+    pub old_holder: Address,
+    pub new_holder: Address,
+    pub rotated_by: Address,
+    pub credential_count: u32,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialRenewed {
+    pub credential_id: U256,
+    pub renewed_by: Address,
+    pub old_expires_at: u64,
+    pub new_expires_at: u64,
+    pub timestamp: u64,
+}
+
 #[odra::event]
 pub struct CredentialVerified {
     pub credential_id: U256,
@@ -49,6 +205,32 @@ pub struct AccessLevelChanged {
     pub timestamp: u64,
 }
 
+#[odra::event]
+pub struct RoleGranted {
+    pub account: Address,
+    pub role: Role,
+    pub granted_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct RoleRevoked {
+    pub account: Address,
+    pub role: Role,
+    pub revoked_by: Address,
+    pub timestamp: u64,
+}
+
+/// Emitted per role when a grant is created with an end date
+#[odra::event]
+pub struct TimedAccessGranted {
+    pub account: Address,
+    pub role: Role,
+    pub valid_until: u64,
+    pub granted_by: Address,
+    pub timestamp: u64,
+}
+
 #[odra::event]
 pub struct SuspiciousActivity {
     pub actor: Address,
@@ -57,6 +239,20 @@ pub struct SuspiciousActivity {
     pub timestamp: u64,
 }
 
+#[odra::event]
+pub struct OwnershipTransferProposed {
+    pub current_owner: Address,
+    pub proposed_owner: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct OwnershipTransferCancelled {
+    pub current_owner: Address,
+    pub cancelled_owner: Address,
+    pub timestamp: u64,
+}
+
 #[odra::event]
 pub struct OwnershipTransferred {
     pub previous_owner: Address,
@@ -101,10 +297,45 @@ pub enum Error {
     RevokedCredential = 9,
     ContractPaused = 10,
     InvalidDID = 11,
+    ProposalNotFound = 12,
+    ProposalExpired = 13,
+    ProposalAlreadyExecuted = 14,
+    ThresholdNotMet = 15,
+    SchemaNotFound = 16,
+    SchemaDeprecated = 17,
+    RequestNotFound = 18,
+    AssessmentNotFound = 19,
+    AssessmentAlreadyUsed = 20,
+    // Malformed DIDs, one per `validation::DidError`
+    DidMissingScheme = 21,
+    DidInvalidMethod = 22,
+    DidInvalidIdentifier = 23,
+    // Malformed IPFS CIDs, one per `validation::CidError`
+    CidEmpty = 24,
+    CidUnsupportedMultibase = 25,
+    CidInvalidEncoding = 26,
+    CidUnsupportedVersion = 27,
+    CidInvalidMultihash = 28,
+    // Malformed hex hashes, one per `validation::HashError`
+    HashInvalidLength = 29,
+    HashNotHex = 30,
 }
 
 // ================ DATA STRUCTURES ================
 
+/// Lifecycle state of a credential (revocation is tracked separately).
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum CredentialStatus {
+    Active = 0,
+    /// Waiting for the holder to accept it.
+    Pending = 1,
+    /// Declined by the holder or a reviewer; never becomes valid.
+    Rejected = 2,
+    /// AI confidence below the issuer's threshold; waiting for a reviewer.
+    NeedsReview = 3,
+}
+
 /// W3C Verifiable Credential Structure
 #[odra::odra_type]
 pub struct VerifiableCredential {
@@ -112,288 +343,636 @@ pub struct VerifiableCredential {
     pub issuer_address: Address,
     pub holder_did: String,
     pub holder_address: Address,
-    pub credential_hash: String,
-    pub issuer_signature: String,
+    pub credential_hash: [u8; 32],
+    pub issuer_signature: SignatureBytes,
     pub issued_at: u64,
     pub expires_at: u64,
     pub ai_confidence: u8,
     pub ipfs_hash: String,
     pub revoked: bool,
+    pub schema_id: u32,
+    /// Not valid before this instant (equals `issued_at` unless set explicitly).
+    pub valid_from: u64,
+    /// Credential this one replaced.
+    pub supersedes: Option<U256>,
+    /// Credential that replaced this one.
+    pub superseded_by: Option<U256>,
+    pub status: CredentialStatus,
+    /// Model and assessment behind `ai_confidence`, when supplied.
+    pub ai_provenance: Option<AiProvenance>,
+    /// Oracle assessment `ai_confidence` was taken from.
+    pub ai_assessment_id: u32,
+    /// Co-signatures by other issuers, in endorsement order.
+    pub endorsements: Vec<Endorsement>,
+    /// Endorsements needed before the credential verifies.
+    pub required_endorsements: u32,
+    /// Bounded on-chain attributes, e.g. `role`; see `get_credential_claim`.
+    pub claims: Vec<Claim>,
+    /// Merkle root over the salted claims of the VC document, for
+    /// selective disclosure through `verify_claim`.
+    pub claims_root: Option<[u8; 32]>,
+    /// Position of the credential's revocation bit in the status list.
+    pub status_list_index: u32,
 }
 
-/// Audit Log Entry
-#[odra::odra_type]
-pub struct AuditLog {
-    pub action: String,
-    pub actor: Address,
-    pub timestamp: u64,
-    pub details: String,
+impl VerifiableCredential {
+    /// True when `valid_from <= time < expires_at`.
+    pub fn is_in_validity_window(&self, time: u64) -> bool {
+        self.valid_from <= time && time < self.expires_at
+    }
+
+    pub fn has_required_endorsements(&self) -> bool {
+        self.endorsements.len() as u32 >= self.required_endorsements
+    }
+
+    /// `credential_hash` in the lowercase hex form the entry points take.
+    pub fn credential_hash_hex(&self) -> String {
+        encoding::encode_hex(&self.credential_hash)
+    }
+
+    pub fn issuer_signature_hex(&self) -> String {
+        self.issuer_signature.to_hex()
+    }
 }
 
-/// Rate Limit Data (combines last_issue_time + issue_count)
+/// Another issuer's signature over a credential's hash
 #[odra::odra_type]
-pub struct RateLimitData {
-    pub last_issue_time: u64,
-    pub issue_count: u32,
+pub struct Endorsement {
+    pub endorser: Address,
+    pub endorser_did: String,
+    pub signature: SignatureBytes,
+    pub endorsed_at: u64,
 }
 
-/// Verification Data (combines verification_count + blocked_until)
+/// Arguments of one credential in `issue_credentials_batch`
 #[odra::odra_type]
-pub struct VerificationData {
-    pub verification_count: u32,
-    pub blocked_until: u64,
+pub struct CredentialInput {
+    pub issuer_did: String,
+    pub holder_did: String,
+    pub holder_address: Address,
+    pub credential_hash: String,
+    pub issuer_signature: String,
+    pub ipfs_hash: String,
+    /// Oracle assessment of `credential_hash`; supplies the AI confidence.
+    pub ai_assessment_id: u32,
+    pub expires_in_days: u64,
+    pub schema_id: u32,
+    /// Absolute start of validity; defaults to the issuance time.
+    pub valid_from: Option<u64>,
+    /// Absolute expiry; when set, `expires_in_days` is ignored.
+    pub expires_at: Option<u64>,
+    pub ai_provenance: Option<AiProvenance>,
+    pub claims: Vec<Claim>,
+    /// Hex-encoded `merkle::ClaimTree` root, when the holder may disclose
+    /// claims selectively.
+    pub claims_root: Option<String>,
+    /// Endorsements needed before the credential verifies (at most
+    /// `MAX_ENDORSEMENTS`); set here so it is never valid without them.
+    pub required_endorsements: u32,
 }
 
-// ================ MAIN CONTRACT (14 fields - under limit!) ================
+// ================ MAIN CONTRACT (15 fields - Odra's maximum) ================
 
 #[odra::module]
 pub struct CasperCredIQ {
-    owner: Var<Address>,
+    ownership: SubModule<Ownership>,
+    council: SubModule<AdminCouncil>,
     paused: Var<bool>,
     credential_counter: Var<U256>,
     
     // Main storage
     credentials: Mapping<U256, VerifiableCredential>,
     
-    // Access control
-    access_level: Mapping<Address, u8>,
+    // Access control (named roles, several per address)
+    access: SubModule<AccessControl>,
+    
+    // Holder/issuer indexes (grouped into one submodule)
+    index: SubModule<CredentialIndex>,
+    
+    // Issuer DID registry, with the did:casper documents
+    issuers: SubModule<IssuerRegistry>,
+    
+    // Credential schemas
+    schemas: SubModule<SchemaRegistry>,
+    
+    // Issuance quotas (single and batch)
+    rate_limit: SubModule<RateLimiter>,
     
-    // Indexes (combined into 2 mappings instead of 4)
-    holder_credentials: Mapping<(Address, u32), U256>,  // (holder, index) -> cred_id
-    issuer_credentials: Mapping<(Address, u32), U256>,  // (issuer, index) -> cred_id
+    // Audit logs (entries and counts)
+    audit: SubModule<AuditTrail>,
     
-    // Counts stored as single values (not separate mappings)
-    holder_count: Mapping<Address, u32>,
-    issuer_count: Mapping<Address, u32>,
+    // Lifecycle settings (renewal limits, holder acceptance)
+    policy: SubModule<CredentialPolicy>,
     
-    // Rate limiting (combined into single struct)
-    rate_limit: Mapping<Address, RateLimitData>,
+    // Holder credential requests
+    requests: SubModule<RequestQueue>,
     
-    // Audit logs
-    audit_logs: Mapping<(U256, u32), AuditLog>,
-    audit_count: Mapping<U256, u32>,
+    // AI oracle assessments
+    ai: SubModule<AiAssessments>,
     
-    // Security (combined verification tracking)
-    verification_data: Mapping<Address, VerificationData>,
-    suspicious_activity: Mapping<Address, u32>,
+    // Security (pause switch, verification throttling, suspicious activity)
+    security: SubModule<SecurityMonitor>,
 }
 
 #[odra::module]
 impl CasperCredIQ {
     pub fn init(&mut self) {
         let deployer = self.env().caller();
-        self.owner.set(deployer);
-        self.access_level.set(&deployer, 4);
+        self.ownership.init_owner(deployer);
+        self.access.set_access_level(&deployer, 4, None);
         self.paused.set(false);
         self.credential_counter.set(U256::zero());
     }
 
     // ================ EMERGENCY CONTROLS ================
 
+    /// Guardians can always pause; otherwise this is an owner action.
     pub fn pause(&mut self) {
         let caller = self.env().caller();
-        let owner = self.owner.get().unwrap();
         
-        if caller != owner {
-            self.env().revert(Error::NotOwner);
+        if !self.access.has_role(&caller, Role::Guardian) {
+            self.check_owner_action(caller);
         }
         
-        self.paused.set(true);
-        
-        self.env().emit_event(ContractPaused {
-            paused_by: caller,
-            timestamp: self.env().get_block_time(),
-        });
+        self.apply_pause(caller);
     }
 
     pub fn unpause(&mut self) {
         let caller = self.env().caller();
-        let owner = self.owner.get().unwrap();
-        
-        if caller != owner {
-            self.env().revert(Error::NotOwner);
-        }
-        
-        self.paused.set(false);
-        
-        self.env().emit_event(ContractUnpaused {
-            unpaused_by: caller,
-            timestamp: self.env().get_block_time(),
-        });
+        self.check_owner_action(caller);
+        self.apply_unpause(caller);
     }
 
+    /// Kept for existing clients: now only proposes `new_owner`, who must
+    /// call `accept_ownership` to complete the transfer.
     pub fn transfer_ownership(&mut self, new_owner: Address) {
+        self.propose_owner(new_owner);
+    }
+
+    pub fn propose_owner(&mut self, new_owner: Address) {
+        let caller = self.env().caller();
+        self.check_owner_action(caller);
+        self.apply_propose_owner(new_owner);
+    }
+
+    pub fn accept_ownership(&mut self) {
         let caller = self.env().caller();
-        let current_owner = self.owner.get().unwrap();
         
-        if caller != current_owner {
-            self.env().revert(Error::NotOwner);
+        if self.ownership.get_pending_owner() != Some(caller) {
+            self.log_suspicious_activity(caller, "Unauthorized ownership claim".to_string(), 5);
+            self.env().revert(Error::NotAuthorized);
         }
         
-        self.access_level.set(&current_owner, 0);
-        self.owner.set(new_owner);
-        self.access_level.set(&new_owner, 4);
+        let previous_owner = self.ownership.complete_transfer(caller);
+        self.access.set_access_level(&previous_owner, 0, None);
+        self.access.set_access_level(&caller, 4, None);
         
         self.env().emit_event(OwnershipTransferred {
-            previous_owner: current_owner,
-            new_owner,
+            previous_owner,
+            new_owner: caller,
             timestamp: self.env().get_block_time(),
         });
     }
 
+    pub fn cancel_ownership_transfer(&mut self) {
+        let caller = self.env().caller();
+        self.check_owner_action(caller);
+        self.apply_cancel_ownership_transfer();
+    }
+
     // ================ OWNER FUNCTIONS ================
 
-    pub fn set_access_level(&mut self, user: Address, level: u8) {
+    /// `valid_until` (block time, ms) bounds the grant; omit it for no end date.
+    pub fn set_access_level(&mut self, user: Address, level: u8, valid_until: Maybe<u64>) {
         self.check_not_paused();
         
         let caller = self.env().caller();
-        let owner = self.owner.get().unwrap();
-        
-        if caller != owner {
-            self.env().revert(Error::NotOwner);
-        }
+        self.check_owner_action(caller);
+        self.apply_access_level(caller, user, level, into_option(valid_until));
+    }
+
+    // ================ ADMIN COUNCIL ================
+
+    /// Sets up the first council (owner only). Once a council exists it can
+    /// only be changed through an `UpdateCouncil` proposal.
+    pub fn set_council(&mut self, members: Vec<Address>, threshold: u32) {
+        let caller = self.env().caller();
+        self.check_owner_action(caller);
         
-        if level > 4 {
+        if threshold == 0 {
             self.env().revert(Error::InvalidInput);
         }
         
-        let old_level = self.access_level.get(&user).unwrap_or(0);
-        self.access_level.set(&user, level);
+        self.council.configure(members, threshold, caller);
+    }
+
+    pub fn propose_council_action(&mut self, action: CouncilAction) -> u32 {
+        let caller = self.env().caller();
+        self.validate_council_action(&action);
+        self.council.propose(caller, action)
+    }
+
+    pub fn approve_council_action(&mut self, proposal_id: u32) {
+        let caller = self.env().caller();
+        self.council.approve(caller, proposal_id);
+    }
+
+    pub fn execute_council_action(&mut self, proposal_id: u32) {
+        let caller = self.env().caller();
+        let action = self.council.take_for_execution(caller, proposal_id);
         
-        self.env().emit_event(AccessLevelChanged {
-            user,
-            old_level,
-            new_level: level,
-            changed_by: caller,
-            timestamp: self.env().get_block_time(),
-        });
+        match action {
+            CouncilAction::Pause => self.apply_pause(caller),
+            CouncilAction::Unpause => self.apply_unpause(caller),
+            CouncilAction::TransferOwnership { new_owner } => self.apply_propose_owner(new_owner),
+            CouncilAction::CancelOwnershipTransfer => self.apply_cancel_ownership_transfer(),
+            CouncilAction::SetAccessLevel { user, level, valid_until } => {
+                self.check_not_paused();
+                self.apply_access_level(caller, user, level, valid_until);
+            }
+            CouncilAction::UpdateCouncil { members, threshold } => {
+                self.council.configure(members, threshold, caller);
+            }
+            CouncilAction::GrantRole { account, role, valid_until } => {
+                self.check_not_paused();
+                self.apply_grant_role(caller, account, role, valid_until);
+            }
+            CouncilAction::RevokeRole { account, role } => {
+                self.check_not_paused();
+                self.apply_revoke_role(caller, account, role);
+            }
+            CouncilAction::SetRequiredEndorsements { credential_id, required } => {
+                self.check_not_paused();
+                self.apply_required_endorsements(caller, credential_id, required);
+            }
+        }
     }
 
-    // ================ CREDENTIAL FUNCTIONS ================
+    pub fn get_council_members(&self) -> Vec<Address> {
+        self.council.get_members()
+    }
 
-    pub fn issue_credential(
-        &mut self,
-        issuer_did: String,
-        holder_did: String,
-        holder_address: Address,
-        credential_hash: String,
-        issuer_signature: String,
-        ipfs_hash: String,
-        ai_confidence: u8,
-        expires_in_days: u64,
-    ) -> U256 {
+    pub fn get_council_threshold(&self) -> u32 {
+        self.council.get_threshold()
+    }
+
+    pub fn get_council_proposal(&self, proposal_id: u32) -> Option<CouncilProposal> {
+        self.council.get_proposal(proposal_id)
+    }
+
+    pub fn has_approved_council_action(&self, proposal_id: u32, member: Address) -> bool {
+        self.council.has_approved(proposal_id, &member)
+    }
+    // ================ ROLE MANAGEMENT ================
+
+    /// `valid_until` (block time, ms) bounds the grant; omit it for no end date.
+    pub fn grant_role(&mut self, account: Address, role: Role, valid_until: Maybe<u64>) {
         self.check_not_paused();
         
         let caller = self.env().caller();
-        let current_time = self.env().get_block_time();
+        self.check_can_manage_role(caller, role);
+        self.apply_grant_role(caller, account, role, into_option(valid_until));
+    }
+
+    pub fn revoke_role(&mut self, account: Address, role: Role) {
+        self.check_not_paused();
         
-        // Validate inputs
-        if ai_confidence > 100 {
-            self.env().revert(Error::InvalidInput);
-        }
+        let caller = self.env().caller();
+        self.check_can_manage_role(caller, role);
+        self.apply_revoke_role(caller, account, role);
+    }
+
+    pub fn has_role(&self, account: Address, role: Role) -> bool {
+        self.access.has_role(&account, role)
+    }
+
+    pub fn get_roles(&self, account: Address) -> Vec<Role> {
+        self.access.roles_of(&account)
+    }
+
+    /// Lists every grant held by `account` with its end date, if any.
+    pub fn get_role_grants(&self, account: Address) -> Vec<RoleGrant> {
+        self.access.grants_of(&account)
+    }
+
+    // ================ ISSUER REGISTRY ================
+
+    pub fn register_issuer(
+        &mut self,
+        did: String,
+        controller: Address,
+        name: String,
+        metadata_ipfs_hash: String,
+    ) {
+        self.check_not_paused();
         
-        if credential_hash.len() != 64 {
-            self.env().revert(Error::InvalidInput);
-        }
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.issuers.register(did, controller, name, metadata_ipfs_hash, caller);
+    }
+
+    /// Callable by the DID's controller or an admin.
+    pub fn update_issuer_metadata(&mut self, did: String, name: String, metadata_ipfs_hash: String) {
+        self.check_not_paused();
         
-        if issuer_signature.len() < 64 {
-            self.env().revert(Error::InvalidSignature);
-        }
+        let caller = self.env().caller();
+        self.check_issuer_controller_or_admin(caller, &did);
+        self.issuers.update_metadata(did, name, metadata_ipfs_hash, caller);
+    }
+
+    pub fn set_issuer_status(&mut self, did: String, status: IssuerStatus) {
+        self.check_not_paused();
         
-        if ipfs_hash.len() < 10 {
-            self.env().revert(Error::InvalidInput);
-        }
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.issuers.set_status(did, status, caller);
+    }
+
+    pub fn get_issuer(&self, did: String) -> Option<IssuerRecord> {
+        self.issuers.get(&did)
+    }
+
+    /// Registers a key that may sign credentials for `did` (controller or admin).
+    /// Returns the key index.
+    pub fn add_issuer_key(&mut self, did: String, public_key: PublicKey) -> u32 {
+        self.check_not_paused();
         
-        if !issuer_did.starts_with("did:") || issuer_did.len() < 10 {
-            self.env().revert(Error::InvalidDID);
-        }
+        let caller = self.env().caller();
+        self.check_issuer_controller_or_admin(caller, &did);
+        self.issuers.add_key(did, public_key, caller)
+    }
+
+    pub fn revoke_issuer_key(&mut self, did: String, key_index: u32) {
+        self.check_not_paused();
         
-        if !holder_did.starts_with("did:") || holder_did.len() < 10 {
-            self.env().revert(Error::InvalidDID);
-        }
+        let caller = self.env().caller();
+        self.check_issuer_controller_or_admin(caller, &did);
+        self.issuers.revoke_key(did, key_index, caller);
+    }
+
+    pub fn get_issuer_keys(&self, did: String) -> Vec<IssuerKey> {
+        self.issuers.keys_of(&did)
+    }
+
+    // ================ DID REGISTRY ================
+
+    /// Creates a did:casper DID with `public_key` as its first verification
+    /// method. It is controlled by the caller, except for a registered issuer
+    /// DID: only its controller (or an admin) can create that, and the
+    /// document always belongs to the controller.
+    pub fn create_did(&mut self, did: String, public_key: PublicKey) {
+        self.check_not_paused();
         
-        // Access control
-        let caller_level = self.access_level.get(&caller).unwrap_or(0);
-        if caller_level < 2 && caller != self.owner.get().unwrap() {
-            self.log_suspicious_activity(caller, "Unauthorized issue attempt".to_string(), 3);
-            self.env().revert(Error::NotAuthorized);
-        }
+        let caller = self.env().caller();
+        self.check_did_syntax(&did);
+        let controller = match self.issuers.get(&did) {
+            Some(record) => {
+                if caller != record.controller {
+                    self.check_admin(caller);
+                }
+                record.controller
+            }
+            // Any other DID must be the caller's own account DID, so nobody
+            // can claim an identifier ahead of the account it belongs to
+            None if did == account_did(&caller) => caller,
+            None => {
+                self.log_suspicious_activity(caller, "Unauthorized DID creation".to_string(), 4);
+                self.env().revert(Error::NotAuthorized)
+            }
+        };
+        self.issuers.dids_mut().create(did, controller, public_key);
+    }
+
+    pub fn rotate_did_key(&mut self, did: String, new_public_key: PublicKey) {
+        self.check_not_paused();
         
-        // Rate limiting
-        self.check_rate_limit(caller, current_time);
+        let caller = self.env().caller();
+        self.check_did_controller(caller, &did);
+        self.issuers.dids_mut().rotate_key(did, new_public_key);
+    }
+
+    pub fn set_did_service(&mut self, did: String, service_id: String, service_type: String, endpoint: String) {
+        self.check_not_paused();
         
-        // Generate ID
-        let credential_id = self.credential_counter.get().unwrap();
-        self.credential_counter.set(credential_id + U256::one());
+        let caller = self.env().caller();
+        self.check_did_controller(caller, &did);
+        self.issuers.dids_mut().set_service(did, ServiceEndpoint {
+            id: service_id,
+            service_type,
+            endpoint,
+        });
+    }
+
+    pub fn remove_did_service(&mut self, did: String, service_id: String) {
+        self.check_not_paused();
         
-        // Calculate expiry
-        let expires_at = current_time + (expires_in_days * 24 * 60 * 60 * 1000);
+        let caller = self.env().caller();
+        self.check_did_controller(caller, &did);
+        self.issuers.dids_mut().remove_service(did, service_id);
+    }
+
+    /// Permanent: credentials can no longer be issued to or by the DID.
+    pub fn deactivate_did(&mut self, did: String) {
+        self.check_not_paused();
         
-        // Create credential
-        let vc = VerifiableCredential {
-            issuer_did: issuer_did.clone(),
-            issuer_address: caller,
-            holder_did: holder_did.clone(),
-            holder_address,
-            credential_hash: credential_hash.clone(),
-            issuer_signature: issuer_signature.clone(),
-            issued_at: current_time,
-            expires_at,
-            ai_confidence,
-            ipfs_hash: ipfs_hash.clone(),
-            revoked: false,
-        };
+        let caller = self.env().caller();
+        self.check_did_controller(caller, &did);
+        self.issuers.dids_mut().deactivate(did, caller);
+    }
+
+    pub fn resolve_did(&self, did: String) -> Option<DidDocument> {
+        self.issuers.dids().resolve(did)
+    }
+
+    /// Admin-only: lets credentials use DIDs of another method, e.g. "web".
+    pub fn set_external_did_method(&mut self, method: String, allowed: bool) {
+        self.check_not_paused();
         
-        self.credentials.set(&credential_id, vc);
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.issuers.dids_mut().set_external_method(method, allowed, caller);
+    }
+
+    pub fn get_external_did_methods(&self) -> Vec<String> {
+        self.issuers.dids().external_methods()
+    }
+
+    // ================ SCHEMA REGISTRY ================
+
+    /// Issuers (or the owner) register a credential schema; returns its id.
+    pub fn register_schema(
+        &mut self,
+        name: String,
+        version: String,
+        schema_hash: String,
+        ipfs_uri: String,
+    ) -> u32 {
+        self.check_not_paused();
         
-        // Add to holder index
-        let holder_idx = self.holder_count.get(&holder_address).unwrap_or(0);
-        self.holder_credentials.set(&(holder_address, holder_idx), credential_id);
-        self.holder_count.set(&holder_address, holder_idx + 1);
+        let caller = self.env().caller();
+        if !self.access.has_role(&caller, Role::Issuer) && !self.ownership.is_owner(&caller) {
+            self.log_suspicious_activity(caller, "Unauthorized schema registration".to_string(), 3);
+            self.env().revert(Error::NotAuthorized);
+        }
         
-        // Add to issuer index
-        let issuer_idx = self.issuer_count.get(&caller).unwrap_or(0);
-        self.issuer_credentials.set(&(caller, issuer_idx), credential_id);
-        self.issuer_count.set(&caller, issuer_idx + 1);
+        self.schemas.register(name, version, schema_hash, ipfs_uri, caller)
+    }
+
+    /// The registering account or an admin can deprecate a schema.
+    pub fn deprecate_schema(&mut self, schema_id: u32) {
+        self.check_not_paused();
         
-        // Update rate limit
-        let mut rl = self.rate_limit.get(&caller).unwrap_or(RateLimitData {
-            last_issue_time: 0,
-            issue_count: 0,
-        });
-        rl.last_issue_time = current_time;
-        rl.issue_count += 1;
-        self.rate_limit.set(&caller, rl);
+        let caller = self.env().caller();
+        if caller != self.schemas.get_or_revert(schema_id).registered_by {
+            self.check_admin(caller);
+        }
+        self.schemas.deprecate(schema_id, caller);
+    }
+
+    pub fn get_schema(&self, schema_id: u32) -> Option<CredentialSchema> {
+        self.schemas.get(schema_id)
+    }
+
+    pub fn get_schema_count(&self) -> u32 {
+        self.schemas.count()
+    }
+
+    /// Up to 50 schemas starting at id `offset`.
+    pub fn list_schemas(&self, offset: u32, limit: u32) -> Vec<CredentialSchema> {
+        self.schemas.list(offset, limit)
+    }
+
+    pub fn get_schema_credential_count(&self, schema_id: u32) -> u32 {
+        self.schemas.credential_count(schema_id)
+    }
+
+    // ================ CREDENTIAL FUNCTIONS ================
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn issue_credential(
+        &mut self,
+        issuer_did: String,
+        holder_did: String,
+        holder_address: Address,
+        credential_hash: String,
+        issuer_signature: String,
+        ipfs_hash: String,
+        ai_assessment_id: u32,
+        expires_in_days: u64,
+        schema_id: u32,
+        ai_provenance: Maybe<AiProvenance>,
+        claims: Maybe<Vec<Claim>>,
+        claims_root: Maybe<String>,
+        required_endorsements: Maybe<u32>,
+    ) -> U256 {
+        self.check_not_paused();
         
-        // Create audit log
-        self.add_audit_log(
-            credential_id,
-            "ISSUED".to_string(),
-            caller,
-            current_time,
-            "Credential issued successfully".to_string(),
-        );
+        let caller = self.env().caller();
+        let input = CredentialInput {
+            issuer_did,
+            holder_did,
+            holder_address,
+            credential_hash,
+            issuer_signature,
+            ipfs_hash,
+            ai_assessment_id,
+            expires_in_days,
+            schema_id,
+            valid_from: None,
+            expires_at: None,
+            ai_provenance: into_option(ai_provenance),
+            claims: claims.unwrap_or_default(),
+            claims_root: into_option(claims_root),
+            required_endorsements: required_endorsements.unwrap_or_default(),
+        };
         
-        self.env().emit_event(CredentialIssued {
-            credential_id,
-            holder: holder_address,
-            issuer: caller,
+        self.issue_single(caller, input)
+    }
+
+    /// Like `issue_credential`, but with absolute `valid_from` and `expires_at`
+    /// timestamps (block time, in milliseconds). `valid_from` may be in the future.
+    #[allow(clippy::too_many_arguments)]
+    pub fn issue_credential_with_validity(
+        &mut self,
+        issuer_did: String,
+        holder_did: String,
+        holder_address: Address,
+        credential_hash: String,
+        issuer_signature: String,
+        ipfs_hash: String,
+        ai_assessment_id: u32,
+        valid_from: u64,
+        expires_at: u64,
+        schema_id: u32,
+        ai_provenance: Maybe<AiProvenance>,
+        claims: Maybe<Vec<Claim>>,
+        claims_root: Maybe<String>,
+        required_endorsements: Maybe<u32>,
+    ) -> U256 {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let input = CredentialInput {
             issuer_did,
             holder_did,
-            ai_confidence,
+            holder_address,
             credential_hash,
+            issuer_signature,
             ipfs_hash,
-            timestamp: current_time,
-        });
+            ai_assessment_id,
+            expires_in_days: 0, // superseded by expires_at
+            schema_id,
+            valid_from: Some(valid_from),
+            expires_at: Some(expires_at),
+            ai_provenance: into_option(ai_provenance),
+            claims: claims.unwrap_or_default(),
+            claims_root: into_option(claims_root),
+            required_endorsements: required_endorsements.unwrap_or_default(),
+        };
         
-        credential_id
+        self.issue_single(caller, input)
+    }
+
+    /// Issues up to `MAX_BATCH_SIZE` credentials in one call. Every input is
+    /// validated before anything is written, and any failure reverts the whole
+    /// batch. Counts against the batch quota rather than the single-issue one.
+    pub fn issue_credentials_batch(&mut self, inputs: Vec<CredentialInput>) -> Vec<U256> {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        
+        if inputs.is_empty() || inputs.len() > MAX_BATCH_SIZE {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        for (i, input) in inputs.iter().enumerate() {
+            self.validate_credential_input(caller, input);
+            
+            // The index only sees earlier batches, so compare within this one too
+            let hash = &input.credential_hash;
+            if inputs[..i].iter().any(|other| other.credential_hash.eq_ignore_ascii_case(hash)) {
+                self.env().revert(Error::AlreadyExists);
+            }
+            if inputs[..i].iter().any(|other| other.ai_assessment_id == input.ai_assessment_id) {
+                self.env().revert(Error::AssessmentAlreadyUsed);
+            }
+        }
+        
+        if !self.rate_limit.record_batch(&caller, inputs.len() as u32) {
+            self.log_suspicious_activity(caller, "Batch rate limit exceeded".to_string(), 4);
+            self.env().revert(Error::RateLimitExceeded);
+        }
+        
+        inputs
+            .into_iter()
+            .map(|input| self.store_credential(caller, input))
+            .collect()
     }
 
     pub fn revoke_credential(&mut self, credential_id: U256, reason: String) {
         self.check_not_paused();
         
         let caller = self.env().caller();
-        let current_time = self.env().get_block_time();
         
         let mut vc = match self.credentials.get(&credential_id) {
             Some(v) => v,
@@ -402,391 +981,4512 @@ impl CasperCredIQ {
         
         let was_already_revoked = vc.revoked;
         
-        let owner = self.owner.get().unwrap();
-        
-        if caller != vc.issuer_address && caller != owner {
+        if caller != vc.issuer_address && !self.ownership.is_owner(&caller) {
             self.log_suspicious_activity(caller, "Unauthorized revoke attempt".to_string(), 4);
             self.env().revert(Error::NotAuthorized);
         }
         
         vc.revoked = true;
+        self.index.mark_status_revoked(vc.status_list_index);
         self.credentials.set(&credential_id, vc);
         
-        self.add_audit_log(
-            credential_id,
-            "REVOKED".to_string(),
-            caller,
-            current_time,
-            reason.clone(),
-        );
-        
-        self.env().emit_event(CredentialRevoked {
-            credential_id,
-            revoked_by: caller,
-            reason,
-            timestamp: current_time,
-            was_already_revoked,
-        });
+        self.record_revocation(credential_id, caller, reason, was_already_revoked);
     }
 
-    // ================ CRYPTOGRAPHIC VERIFICATION ================
-
-    pub fn verify_credential_cryptographic(
+    /// Revokes `old_id` with reason "superseded" and issues its replacement in
+    /// one call, linking the two through `superseded_by`/`supersedes`. Takes
+    /// the same parameters as `issue_credential`; the holder must not change.
+    /// A replacement that starts pending (acceptance or review) only revokes
+    /// `old_id` once it becomes active, so the holder is never left without
+    /// a valid credential.
+    #[allow(clippy::too_many_arguments)]
+    pub fn supersede_credential(
         &mut self,
-        credential_id: U256,
-        provided_hash: String,
-        verification_type: String,
-    ) -> bool {
+        old_id: U256,
+        issuer_did: String,
+        holder_did: String,
+        holder_address: Address,
+        credential_hash: String,
+        issuer_signature: String,
+        ipfs_hash: String,
+        ai_assessment_id: u32,
+        expires_in_days: u64,
+        schema_id: u32,
+        ai_provenance: Maybe<AiProvenance>,
+        claims: Maybe<Vec<Claim>>,
+        claims_root: Maybe<String>,
+        required_endorsements: Maybe<u32>,
+    ) -> U256 {
+        self.check_not_paused();
+        
         let caller = self.env().caller();
-        let current_time = self.env().get_block_time();
         
-        // Get verification data
-        let mut vd = self.verification_data.get(&caller).unwrap_or(VerificationData {
-            verification_count: 0,
-            blocked_until: 0,
+        let old_vc = match self.credentials.get(&old_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        if caller != old_vc.issuer_address && !self.ownership.is_owner(&caller) {
+            self.log_suspicious_activity(caller, "Unauthorized supersede attempt".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        if old_vc.revoked {
+            self.env().revert(Error::RevokedCredential);
+        }
+        
+        if holder_address != old_vc.holder_address {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        let new_id = self.issue_single(caller, CredentialInput {
+            issuer_did,
+            holder_did,
+            holder_address,
+            credential_hash,
+            issuer_signature,
+            ipfs_hash,
+            ai_assessment_id,
+            expires_in_days,
+            schema_id,
+            valid_from: None,
+            expires_at: None,
+            ai_provenance: into_option(ai_provenance),
+            claims: claims.unwrap_or_default(),
+            claims_root: into_option(claims_root),
+            required_endorsements: required_endorsements.unwrap_or_default(),
         });
         
-        // Check if blocked
-        if current_time < vd.blocked_until {
-            self.env().emit_event(CredentialVerified {
-                credential_id,
-                verifier: caller,
-                is_valid: false,
-                verification_type: "BLOCKED".to_string(),
-                timestamp: current_time,
-            });
-            return false;
+        let mut new_vc = self.credentials.get(&new_id).unwrap();
+        new_vc.supersedes = Some(old_id);
+        self.credentials.set(&new_id, new_vc.clone());
+        
+        if new_vc.status == CredentialStatus::Active {
+            self.complete_supersession(new_id, &new_vc, caller);
         }
         
-        // Update verification count
-        vd.verification_count += 1;
+        new_id
+    }
+
+    /// Follows `superseded_by` links from `credential_id` to the newest
+    /// credential in its chain (at most 32 links per call; call again with the
+    /// result if the chain is longer). `None` if the credential does not exist.
+    pub fn get_latest_credential_id(&self, credential_id: U256) -> Option<U256> {
+        let mut current = credential_id;
+        let mut vc = self.credentials.get(&current)?;
         
-        // Block if too many attempts
-        if vd.verification_count > 50 {
-            vd.blocked_until = current_time + (60 * 60 * 1000);
-            self.log_suspicious_activity(caller, "Excessive verification attempts".to_string(), 4);
+        for _ in 0..MAX_SUPERSESSION_HOPS {
+            match vc.superseded_by {
+                Some(next) => {
+                    current = next;
+                    vc = self.credentials.get(&current)?;
+                }
+                None => break,
+            }
         }
         
-        self.verification_data.set(&caller, vd);
+        Some(current)
+    }
+
+    /// Extends a credential's expiry in place, keeping its ID, indexes and
+    /// audit history. `new_expires_at` must be later than the current expiry
+    /// and within the configured maximum renewal length from now.
+    pub fn renew_credential(&mut self, credential_id: U256, new_expires_at: u64) {
+        self.check_not_paused();
         
-        // Get credential
-        let vc = match self.credentials.get(&credential_id) {
+        let caller = self.env().caller();
+        let current_time = self.env().get_block_time();
+        
+        let mut vc = match self.credentials.get(&credential_id) {
             Some(v) => v,
-            None => {
-                self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
-                return false;
-            }
+            None => self.env().revert(Error::CredentialNotFound),
         };
         
-        // Check revoked
+        if caller != vc.issuer_address && !self.ownership.is_owner(&caller) {
+            self.log_suspicious_activity(caller, "Unauthorized renew attempt".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
         if vc.revoked {
-            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
-            return false;
+            self.env().revert(Error::RevokedCredential);
         }
         
-        // Check expired
-        if current_time >= vc.expires_at {
-            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
-            return false;
+        if new_expires_at <= vc.expires_at || new_expires_at <= current_time {
+            self.env().revert(Error::InvalidInput);
         }
         
-        // Check hash
-        if vc.credential_hash != provided_hash {
-            self.log_suspicious_activity(caller, "Hash mismatch during verification".to_string(), 5);
-            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
-            return false;
+        if new_expires_at - current_time > self.policy.max_renewal() {
+            self.env().revert(Error::InvalidInput);
         }
         
-        // Create audit log
+        let old_expires_at = vc.expires_at;
+        vc.expires_at = new_expires_at;
+        self.credentials.set(&credential_id, vc);
+        
         self.add_audit_log(
             credential_id,
-            "VERIFIED".to_string(),
+            "RENEWED".to_string(),
             caller,
             current_time,
-            verification_type.clone(),
+            format!("Expiry extended from {} to {}", old_expires_at, new_expires_at),
         );
         
-        self.emit_verification_event(credential_id, caller, true, verification_type, current_time);
-        true
+        self.env().emit_event(CredentialRenewed {
+            credential_id,
+            renewed_by: caller,
+            old_expires_at,
+            new_expires_at,
+            timestamp: current_time,
+        });
     }
 
-    pub fn verify_credential(&self, credential_id: U256) -> bool {
-        let vc = match self.credentials.get(&credential_id) {
-            Some(v) => v,
-            None => return false,
-        };
-        
-        if vc.revoked {
-            return false;
-        }
+    /// Admin-only: caps how far (ms from the renewal) `renew_credential` may
+    /// push an expiry. 0 disables renewals.
+    pub fn set_max_renewal_period(&mut self, max_renewal: u64) {
+        self.check_not_paused();
         
-        let current_time = self.env().get_block_time();
-        current_time < vc.expires_at
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.policy.set_max_renewal(max_renewal, caller);
     }
 
-    // ================ VIEW FUNCTIONS ================
+    pub fn get_max_renewal_period(&self) -> u64 {
+        self.policy.max_renewal()
+    }
 
-    pub fn get_credential(&self, credential_id: U256) -> Option<VerifiableCredential> {
-        if !self.can_view_credential(credential_id) {
-            return None;
-        }
+    // ================ HOLDER ACCEPTANCE ================
+
+    /// Issuers choose whether their new credentials need holder acceptance.
+    pub fn set_acceptance_required(&mut self, required: bool) {
+        self.check_not_paused();
         
-        let vc = self.credentials.get(&credential_id)?;
+        let caller = self.env().caller();
         
-        let current_time = self.env().get_block_time();
-        if current_time >= vc.expires_at {
-            return None;
+        if !self.access.has_role(&caller, Role::Issuer) && !self.ownership.is_owner(&caller) {
+            self.env().revert(Error::NotAuthorized);
         }
         
-        Some(vc)
+        self.policy.set_acceptance_required(caller, required);
     }
 
-    pub fn is_revoked(&self, credential_id: U256) -> bool {
-        self.credentials.get(&credential_id)
-            .map(|vc| vc.revoked)
-            .unwrap_or(false)
+    pub fn is_acceptance_required(&self, issuer: Address) -> bool {
+        self.policy.acceptance_required(&issuer)
     }
 
-    // ================ INDEX FUNCTIONS ================
-
-    pub fn get_holder_credential_count(&self, holder: Address) -> u32 {
-        self.holder_count.get(&holder).unwrap_or(0)
+    /// The holder accepts a pending credential, which becomes verifiable
+    /// and is added to the holder index.
+    pub fn accept_credential(&mut self, credential_id: U256) {
+        let (caller, mut vc) = self.pending_credential_of_caller(credential_id);
+        let current_time = self.env().get_block_time();
+        
+        vc.status = CredentialStatus::Active;
+        self.credentials.set(&credential_id, vc.clone());
+        self.index.remove_holder_pending(caller, credential_id);
+        self.index.add_holder_credential(caller, credential_id);
+        self.complete_supersession(credential_id, &vc, vc.issuer_address);
+        
+        self.add_audit_log(
+            credential_id,
+            "ACCEPTED".to_string(),
+            caller,
+            current_time,
+            "Credential accepted by holder".to_string(),
+        );
+        
+        self.env().emit_event(CredentialAccepted {
+            credential_id,
+            holder: caller,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn reject_credential(&mut self, credential_id: U256) {
+        let (caller, mut vc) = self.pending_credential_of_caller(credential_id);
+        let current_time = self.env().get_block_time();
+        
+        vc.status = CredentialStatus::Rejected;
+        self.index.mark_status_revoked(vc.status_list_index);
+        self.credentials.set(&credential_id, vc);
+        self.index.remove_holder_pending(caller, credential_id);
+        
+        self.add_audit_log(
+            credential_id,
+            "REJECTED".to_string(),
+            caller,
+            current_time,
+            "Credential rejected by holder".to_string(),
+        );
+        
+        self.env().emit_event(CredentialRejected {
+            credential_id,
+            holder: caller,
+            timestamp: current_time,
+        });
+    }
+
+    // ================ HOLDER ROTATION ================
+
+    /// Recovery for a lost holder key: moves the credentials of `old_address`,
+    /// including those still waiting for acceptance or review, to
+    /// `new_address`. The owner and admins move all of them; an issuer moves
+    /// only the credentials it issued.
+    pub fn rotate_holder(&mut self, old_address: Address, new_address: Address) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let rotates_all = self.ownership.is_owner(&caller) || self.access.has_role(&caller, Role::Admin);
+        
+        if !rotates_all && !self.access.has_role(&caller, Role::Issuer) {
+            self.log_suspicious_activity(caller, "Unauthorized holder rotation".to_string(), 5);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        let issued_by = if rotates_all { None } else { Some(caller) };
+        self.apply_holder_rotation(caller, old_address, new_address, issued_by);
+    }
+
+    /// The holder moves all its credentials to the account of `new_public_key`.
+    /// `signature` is a hex-encoded signature by that key over
+    /// `get_holder_rotation_message(caller)`, proving the new key accepts them.
+    pub fn rotate_own_holder_address(&mut self, new_public_key: PublicKey, signature: String) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let message = self.get_holder_rotation_message(caller);
+        
+        let signed = encoding::decode_signature(&signature)
+            .is_some_and(|signature| self.env().verify_signature(&message, &signature, &new_public_key));
+        if !signed {
+            self.log_suspicious_activity(caller, "Invalid holder rotation signature".to_string(), 5);
+            self.env().revert(Error::InvalidSignature);
+        }
+        
+        self.index.bump_holder_rotation_nonce(caller);
+        self.apply_holder_rotation(caller, caller, Address::from(new_public_key), None);
+    }
+
+    /// Bytes the new key signs for `rotate_own_holder_address`:
+    /// `HOLDER_ROTATION_DOMAIN`, this contract's address, `holder` and its
+    /// current rotation nonce (u64, little-endian), so that a signature is
+    /// only good for one rotation of one holder on one contract.
+    pub fn get_holder_rotation_message(&self, holder: Address) -> Bytes {
+        let mut message = HOLDER_ROTATION_DOMAIN.to_vec();
+        for address in [self.env().self_address(), holder] {
+            match address.to_bytes() {
+                Ok(bytes) => message.extend_from_slice(&bytes),
+                Err(_) => self.env().revert(Error::InvalidInput),
+            }
+        }
+        message.extend_from_slice(&self.index.holder_rotation_nonce(holder).to_le_bytes());
+        Bytes::from(message)
+    }
+
+    pub fn get_holder_rotation_nonce(&self, holder: Address) -> u64 {
+        self.index.holder_rotation_nonce(holder)
+    }
+
+    // ================ ENDORSEMENTS ================
+
+    /// Another authorized issuer co-signs a credential. `signature` is made
+    /// with a key registered for `endorser_did`, over the credential hash,
+    /// exactly like an issuer signature.
+    pub fn endorse_credential(&mut self, credential_id: U256, endorser_did: String, signature: String) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let current_time = self.env().get_block_time();
+        
+        let mut vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        if vc.revoked {
+            self.env().revert(Error::RevokedCredential);
+        }
+        
+        if vc.status == CredentialStatus::Rejected {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        if !self.access.has_role(&caller, Role::Issuer) || !self.issuers.is_active_controller(&endorser_did, &caller) {
+            self.log_suspicious_activity(caller, "Unauthorized endorsement".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        // Endorsements come from issuers other than the one that issued it
+        if caller == vc.issuer_address || endorser_did == vc.issuer_did {
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        if vc.endorsements.iter().any(|e| e.endorser == caller || e.endorser_did == endorser_did) {
+            self.env().revert(Error::AlreadyExists);
+        }
+        
+        if vc.endorsements.len() as u32 >= MAX_ENDORSEMENTS {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        let signature = match SignatureBytes::from_hex(&signature) {
+            Some(s) if self.verify_issuer_signature(&endorser_did, &vc.credential_hash, &s) => s,
+            _ => {
+                self.log_suspicious_activity(caller, "Invalid endorsement signature".to_string(), 5);
+                self.env().revert(Error::InvalidSignature)
+            }
+        };
+        vc.endorsements.push(Endorsement {
+            endorser: caller,
+            endorser_did: endorser_did.clone(),
+            signature,
+            endorsed_at: current_time,
+        });
+        let endorsement_count = vc.endorsements.len() as u32;
+        self.credentials.set(&credential_id, vc);
+        
+        self.add_audit_log(
+            credential_id,
+            "ENDORSED".to_string(),
+            caller,
+            current_time,
+            endorser_did.clone(),
+        );
+        
+        self.env().emit_event(CredentialEndorsed {
+            credential_id,
+            endorser: caller,
+            endorser_did,
+            endorsement_count,
+            timestamp: current_time,
+        });
+    }
+
+    /// The issuing address (or the owner) sets how many endorsements the
+    /// credential needs before `verify_credential` accepts it. The issuer can
+    /// only raise it; lowering needs the owner (the council once one is set).
+    pub fn set_required_endorsements(&mut self, credential_id: U256, required: u32) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        if caller != vc.issuer_address && !self.ownership.is_owner(&caller) {
+            self.log_suspicious_activity(caller, "Unauthorized endorsement requirement change".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        // Otherwise the issuer alone could undo a multi-issuer requirement
+        if required < vc.required_endorsements {
+            self.check_owner_action(caller);
+        }
+        
+        self.apply_required_endorsements(caller, credential_id, required);
+    }
+
+    fn apply_required_endorsements(&mut self, caller: Address, credential_id: U256, required: u32) {
+        let mut vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        if required > MAX_ENDORSEMENTS {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        vc.required_endorsements = required;
+        self.credentials.set(&credential_id, vc);
+        
+        self.env().emit_event(EndorsementRequirementChanged {
+            credential_id,
+            required,
+            changed_by: caller,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    pub fn get_endorsements(&self, credential_id: U256) -> Vec<Endorsement> {
+        self.credentials.get(&credential_id)
+            .map(|vc| vc.endorsements)
+            .unwrap_or_default()
+    }
+
+    pub fn get_required_endorsements(&self, credential_id: U256) -> u32 {
+        self.credentials.get(&credential_id)
+            .map(|vc| vc.required_endorsements)
+            .unwrap_or(0)
+    }
+
+    // ================ CREDENTIAL REQUESTS ================
+
+    /// A holder asks the controller of `issuer_did` for a credential. The
+    /// caller becomes the holder address of the credential if approved.
+    pub fn submit_request(
+        &mut self,
+        issuer_did: String,
+        holder_did: String,
+        credential_type: String,
+        evidence_ipfs_hash: String,
+    ) -> u32 {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        
+        self.check_did_syntax(&holder_did);
+        
+        // Same holder DID checks as at issuance, with the requester as holder
+        if !self.issuers.dids().accepts(&holder_did) || !self.issuers.dids().is_bound_to(&holder_did, &caller) {
+            self.env().revert(Error::InvalidDID);
+        }
+        
+        match self.issuers.get(&issuer_did) {
+            Some(record) if record.status == IssuerStatus::Active => {}
+            _ => self.env().revert(Error::InvalidDID),
+        }
+        
+        self.requests.submit(caller, issuer_did, holder_did, credential_type, evidence_ipfs_hash)
+    }
+
+    /// Issues the requested credential through the normal issuance checks
+    /// (the caller must control the request's issuer DID) and closes the request.
+    #[allow(clippy::too_many_arguments)]
+    pub fn approve_request(
+        &mut self,
+        request_id: u32,
+        credential_hash: String,
+        issuer_signature: String,
+        ipfs_hash: String,
+        ai_assessment_id: u32,
+        expires_in_days: u64,
+        schema_id: u32,
+        ai_provenance: Maybe<AiProvenance>,
+        claims: Maybe<Vec<Claim>>,
+        claims_root: Maybe<String>,
+        required_endorsements: Maybe<u32>,
+    ) -> U256 {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let request = self.requests.pending_or_revert(request_id);
+        
+        let credential_id = self.issue_single(caller, CredentialInput {
+            issuer_did: request.issuer_did,
+            holder_did: request.holder_did,
+            holder_address: request.requester,
+            credential_hash,
+            issuer_signature,
+            ipfs_hash,
+            ai_assessment_id,
+            expires_in_days,
+            schema_id,
+            valid_from: None,
+            expires_at: None,
+            ai_provenance: into_option(ai_provenance),
+            claims: claims.unwrap_or_default(),
+            claims_root: into_option(claims_root),
+            required_endorsements: required_endorsements.unwrap_or_default(),
+        });
+        
+        self.requests.mark_approved(request_id, credential_id, caller);
+        credential_id
+    }
+
+    /// The issuer DID's controller (or an admin) declines a pending request.
+    pub fn reject_request(&mut self, request_id: u32, reason: String) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        let request = self.requests.pending_or_revert(request_id);
+        self.check_issuer_controller_or_admin(caller, &request.issuer_did);
+        self.requests.mark_rejected(request_id, reason, caller);
+    }
+
+    pub fn get_request(&self, request_id: u32) -> Option<CredentialRequest> {
+        self.requests.get(request_id)
+    }
+
+    pub fn get_pending_request_count(&self, issuer_did: String) -> u32 {
+        self.requests.pending_count(issuer_did)
+    }
+
+    /// Up to 50 pending requests for `issuer_did`, starting at position `offset`.
+    pub fn get_pending_requests(&self, issuer_did: String, offset: u32, limit: u32) -> Vec<CredentialRequest> {
+        self.requests.pending(issuer_did, offset, limit)
+    }
+
+    // ================ AI ORACLE ================
+
+    /// AI oracles record a confidence score for a credential hash. Issuance
+    /// then references the returned assessment id instead of a raw score.
+    pub fn submit_ai_assessment(&mut self, subject_hash: String, confidence: u8, assessment_hash: String) -> u32 {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        if !self.access.has_role(&caller, Role::AiOracle) {
+            self.log_suspicious_activity(caller, "Unauthorized AI assessment".to_string(), 5);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        self.ai.submit(caller, subject_hash, confidence, assessment_hash)
+    }
+
+    pub fn get_ai_assessment(&self, assessment_id: u32) -> Option<AiAssessment> {
+        self.ai.get(assessment_id)
+    }
+
+    // ================ AI CONFIDENCE REVIEW ================
+
+    /// Admin-only: credentials issued below this AI confidence start in
+    /// `NeedsReview` unless the issuer has its own threshold.
+    pub fn set_min_confidence(&mut self, min_confidence: u8) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.policy.set_global_min_confidence(min_confidence, caller);
+    }
+
+    /// Admin-only: per-issuer threshold; `None` falls back to the global one.
+    pub fn set_issuer_min_confidence(&mut self, issuer: Address, min_confidence: Option<u8>) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        self.check_admin(caller);
+        self.policy.set_issuer_min_confidence(issuer, min_confidence, caller);
+    }
+
+    /// Threshold that applies to credentials from `issuer`.
+    pub fn get_min_confidence(&self, issuer: Address) -> u8 {
+        self.policy.min_confidence(&issuer)
+    }
+
+    /// A reviewer (not the credential's issuer) clears a credential held for
+    /// review. It then becomes active, or pending if the issuer requires
+    /// holder acceptance.
+    pub fn approve_review(&mut self, credential_id: U256) {
+        let (caller, mut vc) = self.credential_for_review(credential_id);
+        let current_time = self.env().get_block_time();
+        
+        let status = self.status_after_review(vc.issuer_address);
+        vc.status = status;
+        self.credentials.set(&credential_id, vc.clone());
+        
+        if status == CredentialStatus::Active {
+            self.index.remove_holder_pending(vc.holder_address, credential_id);
+            self.index.add_holder_credential(vc.holder_address, credential_id);
+            self.complete_supersession(credential_id, &vc, vc.issuer_address);
+        }
+        
+        self.add_audit_log(
+            credential_id,
+            "REVIEW_APPROVED".to_string(),
+            caller,
+            current_time,
+            "Low-confidence credential approved by reviewer".to_string(),
+        );
+        
+        self.env().emit_event(CredentialReviewed {
+            credential_id,
+            reviewer: caller,
+            approved: true,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn reject_review(&mut self, credential_id: U256, reason: String) {
+        let (caller, mut vc) = self.credential_for_review(credential_id);
+        let current_time = self.env().get_block_time();
+        
+        vc.status = CredentialStatus::Rejected;
+        self.index.mark_status_revoked(vc.status_list_index);
+        self.index.remove_holder_pending(vc.holder_address, credential_id);
+        self.credentials.set(&credential_id, vc);
+        
+        self.add_audit_log(
+            credential_id,
+            "REVIEW_REJECTED".to_string(),
+            caller,
+            current_time,
+            reason,
+        );
+        
+        self.env().emit_event(CredentialReviewed {
+            credential_id,
+            reviewer: caller,
+            approved: false,
+            timestamp: current_time,
+        });
+    }
+
+    // ================ CRYPTOGRAPHIC VERIFICATION ================
+
+    pub fn verify_credential_cryptographic(
+        &mut self,
+        credential_id: U256,
+        provided_hash: String,
+        verification_type: String,
+    ) -> bool {
+        let caller = self.env().caller();
+        let current_time = self.env().get_block_time();
+        
+        if !self.record_verification_attempt(credential_id, caller, current_time) {
+            return false;
+        }
+        
+        // Get credential
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => {
+                self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+                return false;
+            }
+        };
+        
+        // Check revoked
+        if vc.revoked {
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        // Check pending or rejected
+        if vc.status != CredentialStatus::Active {
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        // Check co-signatures
+        if !vc.has_required_endorsements() {
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        // Check expired or not yet valid
+        if !vc.is_in_validity_window(current_time) {
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        // Check hash (either hex case)
+        if validation::parse_hex_hash(&provided_hash) != Ok(vc.credential_hash) {
+            self.log_suspicious_activity(caller, "Hash mismatch during verification".to_string(), 5);
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        // Create audit log
+        self.add_audit_log(
+            credential_id,
+            "VERIFIED".to_string(),
+            caller,
+            current_time,
+            verification_type.clone(),
+        );
+        
+        self.emit_verification_event(credential_id, caller, true, verification_type, current_time);
+        true
+    }
+
+    /// Checks a selectively disclosed claim against the credential's claims
+    /// root: `salt` is the claim's hex-encoded 32-byte salt and `proof` the
+    /// hex-encoded siblings from `merkle::ClaimTree::proof_hex`. The credential
+    /// itself must also verify. Throttled like `verify_credential_cryptographic`.
+    pub fn verify_claim(
+        &mut self,
+        credential_id: U256,
+        claim_key: String,
+        claim_value: String,
+        salt: String,
+        proof: Vec<String>,
+    ) -> bool {
+        let caller = self.env().caller();
+        let current_time = self.env().get_block_time();
+        let verification_type = "SELECTIVE_DISCLOSURE".to_string();
+        
+        if !self.record_verification_attempt(credential_id, caller, current_time) {
+            return false;
+        }
+        
+        let root = match self.credentials.get(&credential_id).and_then(|vc| vc.claims_root) {
+            Some(root) if self.verify_credential(credential_id) => root,
+            _ => {
+                self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+                return false;
+            }
+        };
+        
+        // Malformed salts or proof nodes simply fail to verify
+        let salt = validation::parse_hex_hash(&salt);
+        let proof: Result<Vec<[u8; 32]>, _> = proof.iter().map(|node| validation::parse_hex_hash(node)).collect();
+        let included = match (salt, proof) {
+            (Ok(salt), Ok(proof)) => merkle::verify(&root, merkle::leaf_hash(&claim_key, &claim_value, &salt), &proof),
+            _ => false,
+        };
+        
+        if !included {
+            self.log_suspicious_activity(caller, "Invalid claim disclosure".to_string(), 3);
+            self.emit_verification_event(credential_id, caller, false, verification_type, current_time);
+            return false;
+        }
+        
+        self.add_audit_log(
+            credential_id,
+            "VERIFIED".to_string(),
+            caller,
+            current_time,
+            verification_type.clone(),
+        );
+        
+        self.emit_verification_event(credential_id, caller, true, verification_type, current_time);
+        true
+    }
+
+    pub fn verify_credential(&self, credential_id: U256) -> bool {
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => return false,
+        };
+        
+        if vc.revoked || vc.status != CredentialStatus::Active || !vc.has_required_endorsements() {
+            return false;
+        }
+        
+        vc.is_in_validity_window(self.env().get_block_time())
+    }
+
+    // ================ VIEW FUNCTIONS ================
+
+    pub fn get_credential(&self, credential_id: U256) -> Option<VerifiableCredential> {
+        if !self.can_view_credential(credential_id) {
+            return None;
+        }
+        
+        let vc = self.credentials.get(&credential_id)?;
+        
+        if !vc.is_in_validity_window(self.env().get_block_time()) {
+            return None;
+        }
+        
+        Some(vc)
+    }
+
+    /// Value of one claim on a credential, for on-chain checks such as
+    /// `role == "admin"`. `None` unless the credential currently verifies
+    /// (see `verify_credential`) and carries a claim with that key.
+    pub fn get_credential_claim(&self, credential_id: U256, key: String) -> Option<String> {
+        if !self.verify_credential(credential_id) {
+            return None;
+        }
+        
+        let vc = self.credentials.get(&credential_id)?;
+        claims::value_of(&vc.claims, &key).map(String::from)
+    }
+
+    /// True for revoked and rejected credentials, whose status list bits are
+    /// set alike.
+    pub fn is_revoked(&self, credential_id: U256) -> bool {
+        self.credentials.get(&credential_id)
+            .map(|vc| vc.revoked || vc.status == CredentialStatus::Rejected)
+            .unwrap_or(false)
+    }
+
+    // ================ INDEX FUNCTIONS ================
+
+    pub fn get_holder_credential_count(&self, holder: Address) -> u32 {
+        self.index.holder_count(holder)
+    }
+
+    pub fn get_holder_credential_at_index(&self, holder: Address, index: u32) -> Option<U256> {
+        self.index.holder_credential_at(holder, index)
+    }
+
+    /// Looks up a credential from the hash in its VC document.
+    pub fn get_credential_id_by_hash(&self, credential_hash: String) -> Option<U256> {
+        let credential_hash = validation::parse_hex_hash(&credential_hash).ok()?;
+        self.index.credential_by_hash(credential_hash)
+    }
+
+    /// Status list indexes assigned so far; the next credential issued gets
+    /// this one, so issuers can embed it in the VC document beforehand.
+    pub fn get_status_list_length(&self) -> u32 {
+        self.index.status_list_length()
+    }
+
+    /// Up to 1024 bytes of the revocation bitstring from byte `offset`
+    /// (bit `i` is the most significant bit of byte `i / 8`; set means revoked).
+    /// Ends at the last assigned index, so an empty result means `offset` is past it.
+    pub fn get_status_list_chunk(&self, offset: u32, max_bytes: u32) -> Bytes {
+        self.index.status_list_chunk(offset, max_bytes)
+    }
+
+    pub fn get_issuer_credential_count(&self, issuer: Address) -> u32 {
+        self.index.issuer_count(issuer)
+    }
+
+    pub fn get_issuer_credential_at_index(&self, issuer: Address, index: u32) -> Option<U256> {
+        self.index.issuer_credential_at(issuer, index)
+    }
+
+    // ================ AUDIT LOG FUNCTIONS ================
+
+    pub fn get_audit_count(&self, credential_id: U256) -> u32 {
+        self.audit.count(credential_id)
+    }
+
+    pub fn get_audit_log_at_index(&self, credential_id: U256, index: u32) -> Option<AuditLog> {
+        self.audit.get(credential_id, index)
+    }
+
+    // ================ GENERAL GETTERS ================
+
+    pub fn get_suspicious_activity_count(&self, address: Address) -> u32 {
+        self.security.suspicious_count(&address)
+    }
+
+    pub fn get_verification_count(&self, address: Address) -> u32 {
+        self.security.verification_data(&address).verification_count
+    }
+
+    pub fn get_owner(&self) -> Address {
+        self.ownership.get_owner()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<Address> {
+        self.ownership.get_pending_owner()
+    }
+
+    pub fn get_access_level(&self, address: Address) -> u8 {
+        self.access.access_level(&address)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.get().unwrap_or(false)
+    }
+    
+    pub fn get_total_credentials(&self) -> U256 {
+        self.credential_counter.get().unwrap()
+    }
+
+    // ================ INTERNAL HELPERS ================
+
+    fn check_not_paused(&self) {
+        if self.paused.get().unwrap_or(false) {
+            self.env().revert(Error::ContractPaused);
+        }
+    }
+
+    /// Owner-only actions go through the council once one is configured.
+    fn check_owner_action(&self, caller: Address) {
+        if !self.ownership.is_owner(&caller) {
+            self.env().revert(Error::NotOwner);
+        }
+        
+        if self.council.is_active() {
+            self.env().revert(Error::NotAuthorized);
+        }
+    }
+
+    fn validate_council_action(&self, action: &CouncilAction) {
+        let invalid = match action {
+            CouncilAction::TransferOwnership { new_owner } => self.ownership.is_owner(new_owner),
+            CouncilAction::SetAccessLevel { level, .. } => *level > 4,
+            _ => false,
+        };
+        
+        if invalid {
+            self.env().revert(Error::InvalidInput);
+        }
+    }
+
+    fn apply_pause(&mut self, paused_by: Address) {
+        self.paused.set(true);
+        
+        self.env().emit_event(ContractPaused {
+            paused_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    fn apply_unpause(&mut self, unpaused_by: Address) {
+        self.paused.set(false);
+        
+        self.env().emit_event(ContractUnpaused {
+            unpaused_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    fn apply_cancel_ownership_transfer(&mut self) {
+        let cancelled_owner = match self.ownership.get_pending_owner() {
+            Some(p) => p,
+            None => self.env().revert(Error::InvalidInput),
+        };
+        
+        self.ownership.set_pending_owner(None);
+        
+        self.env().emit_event(OwnershipTransferCancelled {
+            current_owner: self.ownership.get_owner(),
+            cancelled_owner,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    fn apply_propose_owner(&mut self, new_owner: Address) {
+        let current_owner = self.ownership.get_owner();
+        
+        if new_owner == current_owner {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        self.ownership.set_pending_owner(Some(new_owner));
+        
+        self.env().emit_event(OwnershipTransferProposed {
+            current_owner,
+            proposed_owner: new_owner,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    fn apply_access_level(&mut self, caller: Address, user: Address, level: u8, valid_until: Option<u64>) {
+        if level > 4 {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        let old_level = self.access.access_level(&user);
+        let (granted, revoked) = self.access.set_access_level(&user, level, valid_until);
+        let timestamp = self.env().get_block_time();
+        
+        self.env().emit_event(AccessLevelChanged {
+            user,
+            old_level,
+            new_level: level,
+            changed_by: caller,
+            timestamp,
+        });
+        
+        for role in granted {
+            self.env().emit_event(RoleGranted {
+                account: user,
+                role,
+                granted_by: caller,
+                timestamp,
+            });
+        }
+        
+        for role in revoked {
+            self.env().emit_event(RoleRevoked {
+                account: user,
+                role,
+                revoked_by: caller,
+                timestamp,
+            });
+        }
+        
+        if let Some(valid_until) = valid_until {
+            for role in access_control::level_roles(level) {
+                self.env().emit_event(TimedAccessGranted {
+                    account: user,
+                    role,
+                    valid_until,
+                    granted_by: caller,
+                    timestamp,
+                });
+            }
+        }
+    }
+
+    fn can_view_credential(&self, credential_id: U256) -> bool {
+        let caller = self.env().caller();
+        
+        if self.ownership.is_owner(&caller) {
+            return true;
+        }
+        
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => return false,
+        };
+        
+        if caller == vc.holder_address {
+            return true;
+        }
+        
+        if caller == vc.issuer_address {
+            return true;
+        }
+        
+        self.access.has_role(&caller, Role::Auditor) || self.access.has_role(&caller, Role::Admin)
+    }
+
+    /// Status of a credential once past (or exempt from) confidence review.
+    fn status_after_review(&self, issuer: Address) -> CredentialStatus {
+        if self.policy.acceptance_required(&issuer) {
+            CredentialStatus::Pending
+        } else {
+            CredentialStatus::Active
+        }
+    }
+
+    /// Loads an unrevoked credential awaiting review, checking the caller is a
+    /// reviewer other than its issuer.
+    fn credential_for_review(&mut self, credential_id: U256) -> (Address, VerifiableCredential) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        
+        if !self.access.has_role(&caller, Role::Reviewer) {
+            self.log_suspicious_activity(caller, "Unauthorized review attempt".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        // Four-eyes: issuers cannot clear their own credentials
+        if caller == vc.issuer_address {
+            self.log_suspicious_activity(caller, "Issuer reviewing own credential".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        if vc.revoked {
+            self.env().revert(Error::RevokedCredential);
+        }
+        
+        if vc.status != CredentialStatus::NeedsReview {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        (caller, vc)
+    }
+
+    /// Loads a pending, unrevoked credential held by the caller.
+    fn pending_credential_of_caller(&mut self, credential_id: U256) -> (Address, VerifiableCredential) {
+        self.check_not_paused();
+        
+        let caller = self.env().caller();
+        
+        let vc = match self.credentials.get(&credential_id) {
+            Some(v) => v,
+            None => self.env().revert(Error::CredentialNotFound),
+        };
+        
+        if caller != vc.holder_address {
+            self.log_suspicious_activity(caller, "Response to credential of another holder".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        if vc.revoked {
+            self.env().revert(Error::RevokedCredential);
+        }
+        
+        if vc.status != CredentialStatus::Pending {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        (caller, vc)
+    }
+
+    fn check_did_controller(&mut self, caller: Address, did: &str) {
+        if self.issuers.dids().active_or_revert(did).controller != caller {
+            self.log_suspicious_activity(caller, "Unauthorized DID update".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+    }
+
+    fn check_issuer_controller_or_admin(&mut self, caller: Address, did: &str) {
+        if caller != self.issuers.get_or_revert(did).controller {
+            self.check_admin(caller);
+        }
+    }
+
+    /// True when `issuer_signature` over the 32 bytes of `credential_hash`
+    /// verifies against a signing key of `issuer_did` (for did:casper, one
+    /// listed in both the issuer registry and the DID document).
+    fn verify_issuer_signature(&self, issuer_did: &str, credential_hash: &[u8; 32], issuer_signature: &SignatureBytes) -> bool {
+        let message = Bytes::from(credential_hash.to_vec());
+        let signature = issuer_signature.to_tagged_bytes();
+        
+        self.issuers
+            .signing_keys(issuer_did)
+            .iter()
+            .any(|key| self.env().verify_signature(&message, &signature, key))
+    }
+
+    /// Moves `old_address`'s credentials, indexed and pending (only those
+    /// issued by `issued_by`, when set), to `new_address`, keeping the rest
+    /// in place.
+    fn apply_holder_rotation(
+        &mut self,
+        caller: Address,
+        old_address: Address,
+        new_address: Address,
+        issued_by: Option<Address>,
+    ) {
+        if old_address == new_address {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        let current_time = self.env().get_block_time();
+        
+        let indexed = self.index.holder_credentials(old_address);
+        let (kept, moved) = self.move_holder_credentials(caller, indexed, new_address, issued_by, current_time);
+        for credential_id in &moved {
+            self.index.add_holder_credential(new_address, *credential_id);
+        }
+        self.index.set_holder_credentials(old_address, kept);
+        
+        let pending = self.index.holder_pending(old_address);
+        let (kept_pending, moved_pending) =
+            self.move_holder_credentials(caller, pending, new_address, issued_by, current_time);
+        for credential_id in &moved_pending {
+            self.index.add_holder_pending(new_address, *credential_id);
+        }
+        self.index.set_holder_pending(old_address, kept_pending);
+        
+        let credential_count = (moved.len() + moved_pending.len()) as u32;
+        if credential_count == 0 {
+            self.env().revert(Error::CredentialNotFound);
+        }
+        
+        self.env().emit_event(HolderRotated {
+            old_holder: old_address,
+            new_holder: new_address,
+            rotated_by: caller,
+            credential_count,
+            timestamp: current_time,
+        });
+    }
+
+    /// Sets the holder of each of `credential_ids` (issued by `issued_by`,
+    /// when set) to `new_address`. Returns the ids left alone and those moved.
+    fn move_holder_credentials(
+        &mut self,
+        caller: Address,
+        credential_ids: Vec<U256>,
+        new_address: Address,
+        issued_by: Option<Address>,
+        current_time: u64,
+    ) -> (Vec<U256>, Vec<U256>) {
+        let mut kept = Vec::new();
+        let mut moved = Vec::new();
+        
+        for credential_id in credential_ids {
+            let mut vc = match self.credentials.get(&credential_id) {
+                Some(v) if issued_by.is_none_or(|issuer| issuer == v.issuer_address) => v,
+                _ => {
+                    kept.push(credential_id);
+                    continue;
+                }
+            };
+            
+            vc.holder_address = new_address;
+            self.credentials.set(&credential_id, vc);
+            moved.push(credential_id);
+            
+            self.add_audit_log(
+                credential_id,
+                "HOLDER_ROTATED".to_string(),
+                caller,
+                current_time,
+                "Holder moved to a new address".to_string(),
+            );
+        }
+        
+        (kept, moved)
+    }
+
+    fn check_admin(&mut self, caller: Address) {
+        if !self.ownership.is_owner(&caller) && !self.access.has_role(&caller, Role::Admin) {
+            self.log_suspicious_activity(caller, "Unauthorized admin action".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+    }
+
+    fn apply_grant_role(&mut self, caller: Address, account: Address, role: Role, valid_until: Option<u64>) {
+        if self.access.grant(&account, role, valid_until) {
+            let timestamp = self.env().get_block_time();
+            
+            self.env().emit_event(RoleGranted {
+                account,
+                role,
+                granted_by: caller,
+                timestamp,
+            });
+            
+            if let Some(valid_until) = valid_until {
+                self.env().emit_event(TimedAccessGranted {
+                    account,
+                    role,
+                    valid_until,
+                    granted_by: caller,
+                    timestamp,
+                });
+            }
+        }
+    }
+
+    fn apply_revoke_role(&mut self, caller: Address, account: Address, role: Role) {
+        if self.access.revoke(&account, role) {
+            self.env().emit_event(RoleRevoked {
+                account,
+                role,
+                revoked_by: caller,
+                timestamp: self.env().get_block_time(),
+            });
+        }
+    }
+
+    /// Admin is owner-managed; the other roles may also be managed by admins.
+    /// The owner's changes go through the council once one is configured,
+    /// even when the owner also holds the Admin role.
+    fn check_can_manage_role(&mut self, caller: Address, role: Role) {
+        if self.ownership.is_owner(&caller) {
+            self.check_owner_action(caller);
+            return;
+        }
+        
+        if role == Role::Admin {
+            self.env().revert(Error::NotOwner);
+        }
+        
+        if !self.access.has_role(&caller, Role::Admin) {
+            self.log_suspicious_activity(caller, "Unauthorized role change attempt".to_string(), 4);
+            self.env().revert(Error::NotAuthorized);
+        }
+    }
+
+    /// Validates and stores one credential against the single-issue quota.
+    fn issue_single(&mut self, caller: Address, input: CredentialInput) -> U256 {
+        self.validate_credential_input(caller, &input);
+        
+        // Rate limiting
+        if !self.rate_limit.record_single(&caller, 1) {
+            self.log_suspicious_activity(caller, "Rate limit exceeded".to_string(), 4);
+            self.env().revert(Error::RateLimitExceeded);
+        }
+        
+        self.store_credential(caller, input)
+    }
+
+    /// Input, authorization, issuer DID and signature checks shared by single
+    /// and batch issuance.
+    fn validate_credential_input(&mut self, caller: Address, input: &CredentialInput) {
+        let credential_hash = self.parse_credential_hash(&input.credential_hash);
+        
+        if let Err(error) = validation::parse_cid(&input.ipfs_hash) {
+            self.env().revert(Error::from(error));
+        }
+        
+        self.validity_window(input);
+        
+        if let Some(provenance) = &input.ai_provenance {
+            if !provenance.is_well_formed() {
+                self.env().revert(Error::InvalidInput);
+            }
+        }
+        
+        if !claims::are_well_formed(&input.claims) {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        self.parse_claims_root(input);
+        
+        if input.required_endorsements > MAX_ENDORSEMENTS {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        self.check_did_syntax(&input.issuer_did);
+        self.check_did_syntax(&input.holder_did);
+        
+        // did:casper DIDs must resolve to active documents; other methods
+        // must be on the allowed list
+        if !self.issuers.dids().accepts(&input.issuer_did) || !self.issuers.dids().accepts(&input.holder_did) {
+            self.env().revert(Error::InvalidDID);
+        }
+        
+        // A did:casper holder DID must belong to the holder address
+        if !self.issuers.dids().is_bound_to(&input.holder_did, &input.holder_address) {
+            self.env().revert(Error::InvalidDID);
+        }
+        
+        self.schemas.check_usable(input.schema_id);
+        
+        // Access control
+        if !self.access.has_role(&caller, Role::Issuer) && !self.ownership.is_owner(&caller) {
+            self.log_suspicious_activity(caller, "Unauthorized issue attempt".to_string(), 3);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        // Caller must control the (active) issuer DID it claims
+        if !self.issuers.is_active_controller(&input.issuer_did, &caller) {
+            self.log_suspicious_activity(caller, "Issuer DID not controlled by caller".to_string(), 4);
+            self.env().revert(Error::InvalidDID);
+        }
+        
+        // Signature over the credential hash must verify against one of the DID's keys
+        let signed = SignatureBytes::from_hex(&input.issuer_signature)
+            .is_some_and(|signature| self.verify_issuer_signature(&input.issuer_did, &credential_hash, &signature));
+        if !signed {
+            self.log_suspicious_activity(caller, "Invalid issuer signature".to_string(), 5);
+            self.env().revert(Error::InvalidSignature);
+        }
+        
+        // Each credential hash can only be issued once
+        if self.index.credential_by_hash(credential_hash).is_some() {
+            self.env().revert(Error::AlreadyExists);
+        }
+        
+        self.check_ai_assessment(caller, input);
+    }
+
+    fn parse_claims_root(&self, input: &CredentialInput) -> Option<[u8; 32]> {
+        let root = input.claims_root.as_ref()?;
+        match validation::parse_hex_hash(root) {
+            Ok(root) => Some(root),
+            Err(_) => self.env().revert(Error::InvalidInput),
+        }
+    }
+
+    /// Reverts with the `Error::Did*` variant matching a malformed DID.
+    fn check_did_syntax(&self, did: &str) {
+        if let Err(error) = validation::parse_did(did) {
+            self.env().revert(Error::from(error));
+        }
+    }
+
+    fn parse_credential_hash(&self, credential_hash: &str) -> [u8; 32] {
+        match validation::parse_hex_hash(credential_hash) {
+            Ok(hash) => hash,
+            Err(error) => self.env().revert(Error::from(error)),
+        }
+    }
+
+    /// The referenced assessment must be unused, about this credential hash,
+    /// from a current AI oracle other than the issuer, and match any
+    /// provenance hash supplied.
+    fn check_ai_assessment(&mut self, caller: Address, input: &CredentialInput) {
+        let assessment = self.ai.unused_or_revert(input.ai_assessment_id);
+        
+        if !assessment.subject_hash.eq_ignore_ascii_case(&input.credential_hash) {
+            self.log_suspicious_activity(caller, "AI assessment for another credential".to_string(), 5);
+            self.env().revert(Error::HashMismatch);
+        }
+        
+        if assessment.oracle == caller || !self.access.has_role(&assessment.oracle, Role::AiOracle) {
+            self.log_suspicious_activity(caller, "AI assessment from untrusted oracle".to_string(), 5);
+            self.env().revert(Error::NotAuthorized);
+        }
+        
+        if let Some(provenance) = &input.ai_provenance {
+            if !provenance.assessment_hash.eq_ignore_ascii_case(&assessment.assessment_hash) {
+                self.env().revert(Error::HashMismatch);
+            }
+        }
+    }
+
+    /// Resolves `(valid_from, expires_at)` for an input. Explicit timestamps must
+    /// give a window that has not already ended; any overflow is rejected.
+    fn validity_window(&self, input: &CredentialInput) -> (u64, u64) {
+        let current_time = self.env().get_block_time();
+        
+        let expires_at = match input.expires_at {
+            Some(expires_at) => Some(expires_at),
+            None => input
+                .expires_in_days
+                .checked_mul(DAY_MS)
+                .and_then(|ms| current_time.checked_add(ms)),
+        };
+        let expires_at = match expires_at {
+            Some(expires_at) => expires_at,
+            None => self.env().revert(Error::InvalidInput),
+        };
+        let valid_from = input.valid_from.unwrap_or(current_time);
+        
+        if (input.valid_from.is_some() || input.expires_at.is_some())
+            && (valid_from >= expires_at || expires_at <= current_time)
+        {
+            self.env().revert(Error::InvalidInput);
+        }
+        
+        (valid_from, expires_at)
+    }
+
+    /// Writes a validated credential, indexes it and emits its audit entry and event.
+    fn store_credential(&mut self, caller: Address, input: CredentialInput) -> U256 {
+        let current_time = self.env().get_block_time();
+        
+        // Generate ID
+        let credential_id = self.credential_counter.get().unwrap();
+        self.credential_counter.set(credential_id + U256::one());
+        
+        // Calculate validity
+        let (valid_from, expires_at) = self.validity_window(&input);
+        
+        let credential_hash = self.parse_credential_hash(&input.credential_hash);
+        let claims_root = self.parse_claims_root(&input);
+        let issuer_signature = match SignatureBytes::from_hex(&input.issuer_signature) {
+            Some(s) => s,
+            None => self.env().revert(Error::InvalidSignature),
+        };
+        
+        let ai_confidence = self.ai.unused_or_revert(input.ai_assessment_id).confidence;
+        let flagged_by_ai = input.ai_provenance.as_ref().is_some_and(|p| p.needs_review());
+        let status = if flagged_by_ai || ai_confidence < self.policy.min_confidence(&caller) {
+            CredentialStatus::NeedsReview
+        } else {
+            self.status_after_review(caller)
+        };
+        
+        // Create credential
+        let vc = VerifiableCredential {
+            issuer_did: input.issuer_did.clone(),
+            issuer_address: caller,
+            holder_did: input.holder_did.clone(),
+            holder_address: input.holder_address,
+            credential_hash,
+            issuer_signature,
+            issued_at: current_time,
+            expires_at,
+            ai_confidence,
+            ipfs_hash: input.ipfs_hash.clone(),
+            revoked: false,
+            schema_id: input.schema_id,
+            valid_from,
+            supersedes: None,
+            superseded_by: None,
+            status,
+            ai_provenance: input.ai_provenance.clone(),
+            ai_assessment_id: input.ai_assessment_id,
+            endorsements: Vec::new(),
+            required_endorsements: input.required_endorsements,
+            claims: input.claims,
+            claims_root,
+            status_list_index: self.index.assign_status_index(),
+        };
+        
+        self.credentials.set(&credential_id, vc);
+        
+        // Add to holder and issuer indexes (pending credentials reach the
+        // holder index once accepted)
+        if status == CredentialStatus::Active {
+            self.index.add_holder_credential(input.holder_address, credential_id);
+        } else {
+            self.index.add_holder_pending(input.holder_address, credential_id);
+        }
+        self.index.add_issuer_credential(caller, credential_id);
+        self.index.add_credential_hash(credential_hash, credential_id);
+        self.ai.mark_used(input.ai_assessment_id, credential_id);
+        self.schemas.record_credential(input.schema_id);
+        
+        // Create audit log
+        self.add_audit_log(
+            credential_id,
+            "ISSUED".to_string(),
+            caller,
+            current_time,
+            "Credential issued successfully".to_string(),
+        );
+        
+        let (ai_model_id, ai_model_version, ai_assessment_hash, ai_recommendation) = match input.ai_provenance {
+            Some(p) => (p.model_id, p.model_version, p.assessment_hash, Some(p.recommendation)),
+            None => (String::new(), String::new(), String::new(), None),
+        };
+        self.env().emit_event(CredentialIssued {
+            credential_id,
+            holder: input.holder_address,
+            issuer: caller,
+            issuer_did: input.issuer_did,
+            holder_did: input.holder_did,
+            ai_confidence,
+            credential_hash: input.credential_hash,
+            ipfs_hash: input.ipfs_hash,
+            timestamp: current_time,
+            schema_id: input.schema_id,
+            ai_model_id,
+            ai_model_version,
+            ai_assessment_hash,
+            ai_recommendation,
+            ai_assessment_id: input.ai_assessment_id,
+        });
+        
+        credential_id
+    }
+
+    fn log_suspicious_activity(&mut self, actor: Address, action: String, severity: u8) {
+        self.security.record_suspicious(&actor);
+        
+        self.env().emit_event(SuspiciousActivity {
+            actor,
+            action,
+            severity,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Revokes the credential `vc` (now active, as `new_id`) replaces and
+    /// links the two. No-op for credentials that replace nothing. When another
+    /// replacement became active first, `vc` loses its `supersedes` link and
+    /// stands on its own, so the chain keeps a single successor.
+    fn complete_supersession(&mut self, new_id: U256, vc: &VerifiableCredential, superseded_by: Address) {
+        let old_id = match vc.supersedes {
+            Some(old_id) => old_id,
+            None => return,
+        };
+        let mut old_vc = match self.credentials.get(&old_id) {
+            Some(v) => v,
+            None => return,
+        };
+        
+        if old_vc.superseded_by.is_some() {
+            let mut vc = vc.clone();
+            vc.supersedes = None;
+            self.credentials.set(&new_id, vc);
+            return;
+        }
+        
+        let was_already_revoked = old_vc.revoked;
+        old_vc.revoked = true;
+        old_vc.superseded_by = Some(new_id);
+        self.index.mark_status_revoked(old_vc.status_list_index);
+        self.credentials.set(&old_id, old_vc);
+        
+        self.record_revocation(old_id, superseded_by, "superseded".to_string(), was_already_revoked);
+        
+        self.env().emit_event(CredentialSuperseded {
+            old_credential_id: old_id,
+            new_credential_id: new_id,
+            superseded_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Adds the REVOKED audit entry and event for a revocation.
+    fn record_revocation(
+        &mut self,
+        credential_id: U256,
+        caller: Address,
+        reason: String,
+        was_already_revoked: bool,
+    ) {
+        let current_time = self.env().get_block_time();
+        
+        self.add_audit_log(
+            credential_id,
+            "REVOKED".to_string(),
+            caller,
+            current_time,
+            reason.clone(),
+        );
+        
+        self.env().emit_event(CredentialRevoked {
+            credential_id,
+            revoked_by: caller,
+            reason,
+            timestamp: current_time,
+            was_already_revoked,
+        });
+    }
+
+    fn add_audit_log(
+        &mut self,
+        credential_id: U256,
+        action: String,
+        actor: Address,
+        timestamp: u64,
+        details: String,
+    ) {
+        let audit_count = self.audit.append(credential_id, AuditLog {
+            action: action.clone(),
+            actor,
+            timestamp,
+            details,
+        });
+        
+        self.env().emit_event(AuditLogCreated {
+            credential_id,
+            action,
+            actor,
+            timestamp,
+            audit_count,
+        });
+    }
+
+    /// Counts a verification attempt by `caller`, blocking it for an hour after
+    /// 50 attempts. Returns false (after emitting a BLOCKED event) while blocked.
+    fn record_verification_attempt(&mut self, credential_id: U256, caller: Address, current_time: u64) -> bool {
+        let mut vd = self.security.verification_data(&caller);
+        
+        if current_time < vd.blocked_until {
+            self.emit_verification_event(credential_id, caller, false, "BLOCKED".to_string(), current_time);
+            return false;
+        }
+        
+        vd.verification_count += 1;
+        
+        // Block if too many attempts
+        if vd.verification_count > 50 {
+            vd.blocked_until = current_time + (60 * 60 * 1000);
+            self.log_suspicious_activity(caller, "Excessive verification attempts".to_string(), 4);
+        }
+        
+        self.security.set_verification_data(&caller, vd);
+        true
+    }
+
+    fn emit_verification_event(
+        &self,
+        credential_id: U256,
+        verifier: Address,
+        is_valid: bool,
+        verification_type: String,
+        timestamp: u64,
+    ) {
+        self.env().emit_event(CredentialVerified {
+            credential_id,
+            verifier,
+            is_valid,
+            verification_type,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use odra::casper_types::bytesrepr::ToBytes;
+    use odra::casper_types::{crypto, SecretKey, U512};
+    use odra::casper_types::{runtime_args, RuntimeArgs};
+    use odra::host::{Deployer, HostEnv, HostRef, NoArgs};
+    use odra::{CallDef, DeployReport};
+    use proptest::prelude::*;
+    use status_list_export::{encode_status_list, status_list_credential, MIN_STATUS_LIST_BYTES};
+    use validation::{CidError, DidError, HashError};
+
+    fn valid_ipfs_hash() -> String {
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()
+    }
+    
+    fn valid_credential_hash() -> String {
+        "a".repeat(64)
+    }
+    
+    /// Distinct credential hashes for tests issuing several credentials.
+    fn numbered_credential_hash(n: u64) -> String {
+        format!("{:064x}", n)
+    }
+    
+    /// Hex-encoded signature by `signer` over the bytes of `credential_hash`.
+    fn sign_hash(env: &HostEnv, signer: Address, credential_hash: &str) -> String {
+        let message = encoding::decode_hex(credential_hash).unwrap();
+        encoding::encode_hex(&env.sign_message(&Bytes::from(message), &signer))
+    }
+    
+    fn valid_signature(env: &HostEnv, signer: Address) -> String {
+        sign_hash(env, signer, &valid_credential_hash())
+    }
+    
+    fn register_test_issuer(env: &HostEnv, contract: &mut CasperCredIQHostRef, controller: Address) {
+        contract.register_issuer(
+            "did:casper:issuer".to_string(),
+            controller,
+            "Test Issuer".to_string(),
+            valid_ipfs_hash(),
+        );
+        contract.add_issuer_key("did:casper:issuer".to_string(), env.public_key(&controller));
+        contract.grant_role(test_oracle(env), Role::AiOracle, Maybe::None);
+        create_test_did(env, contract, "did:casper:issuer", controller);
+        // The first test accounts get their own DIDs, for use as holder DIDs
+        for i in 0..10 {
+            let account = env.get_account(i);
+            create_test_did(env, contract, &account_did(&account), account);
+        }
+    }
+
+    /// Whether the status list bit of `credential_id` is set (read as the owner,
+    /// who can view every credential).
+    fn status_bit_set(contract: &CasperCredIQHostRef, credential_id: U256) -> bool {
+        let env = contract.env().clone();
+        let caller = env.caller();
+        env.set_caller(env.get_account(0));
+        let index = contract.get_credential(credential_id).unwrap().status_list_index;
+        env.set_caller(caller);
+        contract.get_status_list_chunk(index / 8, 1)[0] & (0x80 >> (index % 8)) != 0
+    }
+
+    /// Creates `did` as `controller`, keyed with the controller's public key.
+    fn create_test_did(env: &HostEnv, contract: &mut CasperCredIQHostRef, did: &str, controller: Address) {
+        let caller = env.caller();
+        env.set_caller(controller);
+        contract.create_did(did.to_string(), env.public_key(&controller));
+        env.set_caller(caller);
+    }
+
+    fn test_oracle(env: &HostEnv) -> Address {
+        env.get_account(9)
+    }
+
+    /// Posts an oracle assessment of `credential_hash` and returns its id.
+    fn assess(env: &HostEnv, contract: &mut CasperCredIQHostRef, credential_hash: &str, confidence: u8) -> u32 {
+        let caller = env.caller();
+        env.set_caller(test_oracle(env));
+        let assessment_id = contract.submit_ai_assessment(credential_hash.to_string(), confidence, "e".repeat(64));
+        env.set_caller(caller);
+        assessment_id
+    }
+
+    fn register_test_schema(contract: &mut CasperCredIQHostRef) -> u32 {
+        contract.register_schema(
+            "EmployeeCredential".to_string(),
+            "1.0.0".to_string(),
+            "c".repeat(64),
+            valid_ipfs_hash(),
+        )
+    }
+
+    #[test]
+    fn test_issue_and_verify() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 90);
+        let id = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            valid_credential_hash(),
+            valid_signature(&env, issuer),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        
+        assert_eq!(id, U256::zero());
+        
+        let vc = contract.get_credential(id).unwrap();
+        assert_eq!(vc.ai_confidence, 90);
+        assert!(!vc.revoked);
+        
+        assert!(contract.verify_credential(id));
+    }
+
+    #[test]
+    fn test_revocation() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 95);
+        let id = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            valid_credential_hash(),
+            valid_signature(&env, issuer),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        
+        contract.revoke_credential(id, "Test".to_string());
+        assert!(contract.is_revoked(id));
+        assert!(!contract.verify_credential(id));
+    }
+
+    #[test]
+    fn test_roles() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let admin = env.get_account(1);
+        let auditor = env.get_account(2);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.grant_role(admin, Role::Admin, Maybe::None);
+        
+        // Admins manage the non-admin roles, but not Admin itself
+        env.set_caller(admin);
+        contract.grant_role(auditor, Role::Auditor, Maybe::None);
+        contract.grant_role(auditor, Role::Guardian, Maybe::None);
+        assert_eq!(
+            contract.try_grant_role(auditor, Role::Admin, Maybe::None),
+            Err(Error::NotOwner.into())
+        );
+        
+        assert!(contract.has_role(auditor, Role::Auditor));
+        assert!(!contract.has_role(auditor, Role::Issuer));
+        assert_eq!(contract.get_roles(auditor), vec![Role::Auditor, Role::Guardian]);
+        assert_eq!(contract.get_access_level(auditor), 3);
+        
+        // Legacy levels only touch the level-managed roles
+        env.set_caller(owner);
+        contract.set_access_level(auditor, 0, Maybe::None);
+        assert_eq!(contract.get_roles(auditor), vec![Role::Guardian]);
+        
+        // Guardians can pause but not unpause
+        env.set_caller(auditor);
+        contract.pause();
+        assert!(contract.is_paused());
+        assert_eq!(contract.try_unpause(), Err(Error::NotOwner.into()));
+        
+        assert_eq!(
+            contract.try_revoke_role(auditor, Role::Guardian),
+            Err(Error::ContractPaused.into())
+        );
+    }
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let new_owner = env.get_account(1);
+        let stranger = env.get_account(2);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        
+        // Cancelled proposals leave ownership untouched
+        contract.propose_owner(stranger);
+        assert_eq!(contract.get_pending_owner(), Some(stranger));
+        contract.cancel_ownership_transfer();
+        assert_eq!(contract.get_pending_owner(), None);
+        
+        contract.propose_owner(new_owner);
+        assert_eq!(contract.get_owner(), owner);
+        
+        env.set_caller(stranger);
+        assert_eq!(contract.try_accept_ownership(), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(new_owner);
+        contract.accept_ownership();
+        assert!(env.emitted_event(
+            &contract,
+            OwnershipTransferred {
+                previous_owner: owner,
+                new_owner,
+                timestamp: env.block_time(),
+            }
+        ));
+        
+        assert_eq!(contract.get_owner(), new_owner);
+        assert_eq!(contract.get_pending_owner(), None);
+        assert_eq!(contract.get_access_level(new_owner), 4);
+        assert_eq!(contract.get_access_level(owner), 0);
+    }
+
+    #[test]
+    fn test_council_threshold_edges() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let (a, b, c) = (env.get_account(1), env.get_account(2), env.get_account(3));
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        
+        // Threshold must be 1..=members, members must be distinct
+        assert_eq!(contract.try_set_council(vec![a, b], 0), Err(Error::InvalidInput.into()));
+        assert_eq!(contract.try_set_council(vec![a, b], 3), Err(Error::InvalidInput.into()));
+        assert_eq!(contract.try_set_council(vec![a, a], 2), Err(Error::InvalidInput.into()));
+        
+        // N-of-N: every member must approve
+        contract.set_council(vec![a, b, c], 3);
+        assert_eq!(contract.try_pause(), Err(Error::NotAuthorized.into()));
+        assert_eq!(contract.try_set_council(vec![a], 1), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(a);
+        let id = contract.propose_council_action(CouncilAction::Pause);
+        assert_eq!(contract.try_approve_council_action(id), Err(Error::AlreadyExists.into()));
+        
+        env.set_caller(b);
+        contract.approve_council_action(id);
+        assert_eq!(contract.try_execute_council_action(id), Err(Error::ThresholdNotMet.into()));
+        
+        env.set_caller(owner);
+        assert_eq!(contract.try_approve_council_action(id), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(c);
+        contract.approve_council_action(id);
+        contract.execute_council_action(id);
+        assert!(contract.is_paused());
+        assert_eq!(
+            contract.try_execute_council_action(id),
+            Err(Error::ProposalAlreadyExecuted.into())
+        );
+        
+        // Council may shrink itself to 1-of-1
+        let id = contract.propose_council_action(CouncilAction::UpdateCouncil {
+            members: vec![c],
+            threshold: 1,
+        });
+        env.set_caller(a);
+        contract.approve_council_action(id);
+        env.set_caller(b);
+        contract.approve_council_action(id);
+        contract.execute_council_action(id);
+        
+        assert_eq!(contract.get_council_members(), vec![c]);
+        assert_eq!(contract.try_propose_council_action(CouncilAction::Unpause), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(c);
+        let id = contract.propose_council_action(CouncilAction::Unpause);
+        contract.execute_council_action(id);
+        assert!(!contract.is_paused());
+    }
+
+    #[test]
+    fn test_council_proposal_expiry_and_reconfiguration() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let (a, b) = (env.get_account(1), env.get_account(2));
+        let user = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_council(vec![a, b], 2);
+        
+        env.set_caller(a);
+        assert_eq!(
+            contract.try_propose_council_action(CouncilAction::SetAccessLevel { user, level: 5, valid_until: None }),
+            Err(Error::InvalidInput.into())
+        );
+        let stale = contract.propose_council_action(CouncilAction::SetAccessLevel { user, level: 2, valid_until: None });
+        
+        env.advance_block_time(7 * 24 * 60 * 60 * 1000);
+        env.set_caller(b);
+        assert_eq!(contract.try_approve_council_action(stale), Err(Error::ProposalExpired.into()));
+        
+        // Proposals opened under an older council are void after reconfiguration
+        let pending = contract.propose_council_action(CouncilAction::SetAccessLevel { user, level: 2, valid_until: None });
+        let update = contract.propose_council_action(CouncilAction::UpdateCouncil {
+            members: vec![a, b],
+            threshold: 1,
+        });
+        env.set_caller(a);
+        contract.approve_council_action(pending);
+        contract.approve_council_action(update);
+        contract.execute_council_action(update);
+        assert_eq!(contract.get_council_threshold(), 1);
+        assert_eq!(contract.try_execute_council_action(pending), Err(Error::ProposalExpired.into()));
+        
+        let id = contract.propose_council_action(CouncilAction::TransferOwnership { new_owner: user });
+        contract.execute_council_action(id);
+        assert_eq!(contract.get_pending_owner(), Some(user));
+        assert_eq!(contract.get_owner(), owner);
+    }
+
+    #[test]
+    fn test_council_gates_owner_role_changes() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let (a, b) = (env.get_account(1), env.get_account(2));
+        let (user, successor) = (env.get_account(3), env.get_account(4));
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_council(vec![a, b], 2);
+        assert!(contract.has_role(owner, Role::Admin));
+        
+        // Neither the owner nor the owner's own Admin role bypass the council
+        for role in [Role::Guardian, Role::Issuer, Role::Auditor, Role::Admin] {
+            assert_eq!(contract.try_grant_role(owner, role, Maybe::None), Err(Error::NotAuthorized.into()));
+            assert_eq!(contract.try_grant_role(user, role, Maybe::None), Err(Error::NotAuthorized.into()));
+        }
+        assert_eq!(contract.try_revoke_role(a, Role::Verifier), Err(Error::NotAuthorized.into()));
+        assert_eq!(contract.try_pause(), Err(Error::NotAuthorized.into()));
+        
+        let run = |contract: &mut CasperCredIQHostRef, action: CouncilAction| {
+            env.set_caller(a);
+            let id = contract.propose_council_action(action);
+            env.set_caller(b);
+            contract.approve_council_action(id);
+            contract.execute_council_action(id);
+            env.set_caller(owner);
+        };
+        
+        run(&mut contract, CouncilAction::GrantRole { account: user, role: Role::Guardian, valid_until: None });
+        assert!(contract.has_role(user, Role::Guardian));
+        run(&mut contract, CouncilAction::RevokeRole { account: user, role: Role::Guardian });
+        assert!(!contract.has_role(user, Role::Guardian));
+        
+        // A council-approved transfer can only be cancelled by the council
+        run(&mut contract, CouncilAction::TransferOwnership { new_owner: successor });
+        assert_eq!(contract.try_cancel_ownership_transfer(), Err(Error::NotAuthorized.into()));
+        run(&mut contract, CouncilAction::CancelOwnershipTransfer);
+        env.set_caller(successor);
+        assert_eq!(contract.try_accept_ownership(), Err(Error::NotAuthorized.into()));
+    }
+
+    #[test]
+    fn test_time_bounded_access() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let contractor = env.get_account(1);
+        let auditor = env.get_account(2);
+        let holder = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        
+        let day = 24 * 60 * 60 * 1000;
+        let valid_until = env.block_time() + day;
+        contract.set_access_level(contractor, 2, Maybe::Some(valid_until));
+        register_test_issuer(&env, &mut contract, contractor);
+        let schema_id = register_test_schema(&mut contract);
+        contract.grant_role(auditor, Role::Auditor, Maybe::Some(valid_until));
+        assert!(env.emitted_event(
+            &contract,
+            TimedAccessGranted {
+                account: auditor,
+                role: Role::Auditor,
+                valid_until,
+                granted_by: owner,
+                timestamp: env.block_time(),
+            }
+        ));
+        assert_eq!(
+            contract.try_grant_role(auditor, Role::Auditor, Maybe::Some(env.block_time())),
+            Err(Error::InvalidInput.into())
+        );
+        
+        env.set_caller(contractor);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 80);
+        let id = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            valid_credential_hash(),
+            valid_signature(&env, contractor),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        env.set_caller(auditor);
+        assert!(contract.get_credential(id).is_some());
+        
+        env.advance_block_time(day);
+        
+        assert_eq!(contract.get_access_level(contractor), 0);
+        assert!(contract.get_credential(id).is_none());
+        assert_eq!(
+            contract.get_role_grants(auditor),
+            vec![RoleGrant { role: Role::Auditor, valid_until: Some(valid_until), active: false }]
+        );
+        
+        env.set_caller(contractor);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 80);
+        assert_eq!(
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                valid_credential_hash(),
+                valid_signature(&env, contractor),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            ),
+            Err(Error::NotAuthorized.into())
+        );
+    }
+
+    /// Calls as sent by clients that predate the optional arguments (e.g.
+    /// scripts/test_contract.sh), which leave those arguments out entirely.
+    #[test]
+    fn test_optional_arguments_may_be_omitted() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let user = env.get_account(1);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        let address = contract.contract_address();
+        let call = |entry_point: &str, args: RuntimeArgs| CallDef::new(entry_point, true, args);
+        
+        env.call_contract::<()>(address, call("set_access_level", runtime_args! { "user" => user, "level" => 2u8 }))
+            .unwrap();
+        assert!(contract.has_role(user, Role::Issuer));
+        
+        env.call_contract::<()>(address, call("grant_role", runtime_args! { "account" => user, "role" => Role::Guardian }))
+            .unwrap();
+        assert!(contract.get_role_grants(user).iter().all(|grant| grant.valid_until.is_none()));
+        assert!(contract.has_role(user, Role::Guardian));
+        
+        register_test_issuer(&env, &mut contract, owner);
+        let schema_id = register_test_schema(&mut contract);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 90);
+        let args = runtime_args! {
+            "issuer_did" => "did:casper:issuer".to_string(),
+            "holder_did" => account_did(&user),
+            "holder_address" => user,
+            "credential_hash" => valid_credential_hash(),
+            "issuer_signature" => valid_signature(&env, owner),
+            "ipfs_hash" => valid_ipfs_hash(),
+            "ai_assessment_id" => assessment,
+            "expires_in_days" => 365u64,
+            "schema_id" => schema_id,
+        };
+        let id = env.call_contract::<U256>(address, call("issue_credential", args)).unwrap();
+        let vc = contract.get_credential(id).unwrap();
+        assert_eq!(vc.ai_provenance, None);
+        assert!(vc.claims.is_empty());
+        assert_eq!(vc.claims_root, None);
+    }
+
+    #[test]
+    fn test_issuer_registry() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let impostor = env.get_account(2);
+        let holder = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        contract.set_access_level(impostor, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        assert_eq!(
+            contract.try_register_issuer(
+                "did:casper:issuer".to_string(),
+                impostor,
+                "Copycat".to_string(),
+                valid_ipfs_hash(),
+            ),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        let record = contract.get_issuer("did:casper:issuer".to_string()).unwrap();
+        assert_eq!(record.controller, issuer);
+        assert_eq!(record.status, IssuerStatus::Active);
+        
+        let issue_as = |contract: &mut CasperCredIQHostRef| {
+            let assessment = assess(&env, contract, &valid_credential_hash(), 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                valid_credential_hash(),
+                valid_signature(&env, issuer),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        // Authorized issuers cannot claim a DID they do not control
+        env.set_caller(impostor);
+        assert_eq!(issue_as(&mut contract), Err(Error::InvalidDID.into()));
+        assert_eq!(
+            contract.try_update_issuer_metadata(
+                "did:casper:issuer".to_string(),
+                "Hijacked".to_string(),
+                valid_ipfs_hash(),
+            ),
+            Err(Error::NotAuthorized.into())
+        );
+        
+        env.set_caller(issuer);
+        assert!(issue_as(&mut contract).is_ok());
+        
+        // Suspended DIDs cannot issue; retired DIDs stay retired
+        env.set_caller(owner);
+        contract.set_issuer_status("did:casper:issuer".to_string(), IssuerStatus::Suspended);
+        env.set_caller(issuer);
+        assert_eq!(issue_as(&mut contract), Err(Error::InvalidDID.into()));
+        
+        env.set_caller(owner);
+        contract.set_issuer_status("did:casper:issuer".to_string(), IssuerStatus::Retired);
+        assert_eq!(
+            contract.try_set_issuer_status("did:casper:issuer".to_string(), IssuerStatus::Active),
+            Err(Error::InvalidInput.into())
+        );
+    }
+
+    #[test]
+    fn test_issuer_signature_verification() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let stranger = env.get_account(2);
+        let holder = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue_with = |contract: &mut CasperCredIQHostRef, credential_hash: String, signature: String| {
+            let assessment = assess(&env, contract, &credential_hash, 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                credential_hash,
+                signature,
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        env.set_caller(issuer);
+        assert!(issue_with(&mut contract, valid_credential_hash(), valid_signature(&env, issuer)).is_ok());
+        
+        // Signed by a key not registered for the DID
+        assert_eq!(
+            issue_with(&mut contract, valid_credential_hash(), valid_signature(&env, stranger)),
+            Err(Error::InvalidSignature.into())
+        );
+        
+        // Tampered signature bytes
+        let mut tampered = encoding::decode_hex(&valid_signature(&env, issuer)).unwrap();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert_eq!(
+            issue_with(&mut contract, valid_credential_hash(), encoding::encode_hex(&tampered)),
+            Err(Error::InvalidSignature.into())
+        );
+        
+        // Malformed input
+        assert_eq!(issue_with(&mut contract, valid_credential_hash(), "b".repeat(128)), Err(Error::InvalidSignature.into()));
+        assert_eq!(issue_with(&mut contract, valid_credential_hash(), "zz".to_string()), Err(Error::InvalidSignature.into()));
+        
+        // A secp256k1 key held off-chain by the issuer
+        let secret_key = SecretKey::secp256k1_from_bytes([7u8; 32]).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let key_index = contract.add_issuer_key("did:casper:issuer".to_string(), public_key.clone());
+        assert_eq!(key_index, 1);
+        assert_eq!(
+            contract.try_add_issuer_key("did:casper:issuer".to_string(), public_key.clone()),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        let secp_hash = numbered_credential_hash(1);
+        let message = encoding::decode_hex(&secp_hash).unwrap();
+        let signature = crypto::sign(&message, &secret_key, &public_key);
+        let secp_signature = encoding::encode_hex(&signature.to_bytes().unwrap());
+        
+        // ...which only signs once the DID document lists it as well
+        assert_eq!(
+            issue_with(&mut contract, secp_hash.clone(), secp_signature.clone()),
+            Err(Error::InvalidSignature.into())
+        );
+        contract.rotate_did_key("did:casper:issuer".to_string(), public_key.clone());
+        assert!(issue_with(&mut contract, secp_hash.clone(), secp_signature.clone()).is_ok());
+        assert_eq!(
+            issue_with(&mut contract, numbered_credential_hash(2), sign_hash(&env, issuer, &numbered_credential_hash(2))),
+            Err(Error::InvalidSignature.into())
+        );
+        
+        // Revoked keys no longer verify
+        contract.revoke_issuer_key("did:casper:issuer".to_string(), key_index);
+        assert_eq!(issue_with(&mut contract, secp_hash, secp_signature), Err(Error::InvalidSignature.into()));
+        assert!(!contract.get_issuer_keys("did:casper:issuer".to_string())[1].active);
+        
+        // Only the controller or an admin manages keys
+        env.set_caller(stranger);
+        assert_eq!(
+            contract.try_add_issuer_key("did:casper:issuer".to_string(), env.public_key(&stranger)),
+            Err(Error::NotAuthorized.into())
+        );
+    }
+
+    #[test]
+    fn test_issuer_key_rotation_reuses_slots() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let did = "did:casper:issuer".to_string();
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, owner);
+        
+        // Fill every slot, then keep rotating one key: revoke, add the next
+        for i in 1..8 {
+            assert_eq!(contract.add_issuer_key(did.clone(), env.public_key(&env.get_account(i))), i as u32);
+        }
+        assert_eq!(
+            contract.try_add_issuer_key(did.clone(), env.public_key(&env.get_account(8))),
+            Err(Error::InvalidInput.into())
+        );
+        for i in 8..20 {
+            contract.revoke_issuer_key(did.clone(), 3);
+            assert_eq!(contract.add_issuer_key(did.clone(), env.public_key(&env.get_account(i))), 3);
+        }
+        
+        let keys = contract.get_issuer_keys(did.clone());
+        assert_eq!(keys.len(), 8);
+        assert!(keys.iter().all(|key| key.active));
+        assert_eq!(keys[3].public_key, env.public_key(&env.get_account(19)));
+        
+        // Only active keys count as duplicates
+        assert_eq!(
+            contract.try_add_issuer_key(did.clone(), env.public_key(&env.get_account(19))),
+            Err(Error::AlreadyExists.into())
+        );
+        contract.revoke_issuer_key(did.clone(), 5);
+        assert_eq!(contract.add_issuer_key(did, env.public_key(&env.get_account(5))), 5);
+    }
+
+    #[test]
+    fn test_batch_issuance() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let input_for = |contract: &mut CasperCredIQHostRef, n: u64| CredentialInput {
+            issuer_did: "did:casper:issuer".to_string(),
+            holder_did: account_did(&holder),
+            holder_address: holder,
+            credential_hash: numbered_credential_hash(n),
+            issuer_signature: sign_hash(&env, issuer, &numbered_credential_hash(n)),
+            ipfs_hash: valid_ipfs_hash(),
+            ai_assessment_id: assess(&env, contract, &numbered_credential_hash(n), 90),
+            expires_in_days: 365,
+            schema_id,
+            valid_from: None,
+            expires_at: None,
+            ai_provenance: None,
+            claims: Vec::new(),
+            claims_root: None,
+            required_endorsements: 0,
+        };
+        let inputs_for = |contract: &mut CasperCredIQHostRef, range: core::ops::Range<u64>| {
+            range.map(|n| input_for(contract, n)).collect::<Vec<_>>()
+        };
+        
+        env.set_caller(issuer);
+        let inputs = inputs_for(&mut contract, 0..3);
+        let ids = contract.issue_credentials_batch(inputs.clone());
+        assert_eq!(ids, vec![U256::from(0), U256::from(1), U256::from(2)]);
+        assert_eq!(contract.get_holder_credential_count(holder), 3);
+        for (id, input) in ids.into_iter().zip(inputs) {
+            assert!(contract.verify_credential(id));
+            assert_eq!(contract.get_audit_count(id), 1);
+            assert!(env.emitted_event(
+                &contract,
+                CredentialIssued {
+                    credential_id: id,
+                    holder,
+                    issuer,
+                    issuer_did: input.issuer_did,
+                    holder_did: input.holder_did,
+                    ai_confidence: 90,
+                    credential_hash: input.credential_hash,
+                    ipfs_hash: input.ipfs_hash,
+                    timestamp: env.block_time(),
+                    schema_id,
+                    ai_model_id: String::new(),
+                    ai_model_version: String::new(),
+                    ai_assessment_hash: String::new(),
+                    ai_recommendation: None,
+                    ai_assessment_id: input.ai_assessment_id,
+                }
+            ));
+            assert!(env.emitted_event(
+                &contract,
+                AuditLogCreated {
+                    credential_id: id,
+                    action: "ISSUED".to_string(),
+                    actor: issuer,
+                    timestamp: env.block_time(),
+                    audit_count: 1,
+                }
+            ));
+        }
+        
+        // One bad input reverts the whole batch
+        let mut forged = input_for(&mut contract, 4);
+        forged.issuer_signature = sign_hash(&env, holder, &forged.credential_hash);
+        let inputs = vec![input_for(&mut contract, 3), forged];
+        assert_eq!(
+            contract.try_issue_credentials_batch(inputs),
+            Err(Error::InvalidSignature.into())
+        );
+        assert_eq!(contract.get_total_credentials(), U256::from(3));
+        
+        assert_eq!(contract.try_issue_credentials_batch(vec![]), Err(Error::InvalidInput.into()));
+        let oversized = vec![input_for(&mut contract, 3); MAX_BATCH_SIZE + 1];
+        assert_eq!(
+            contract.try_issue_credentials_batch(oversized),
+            Err(Error::InvalidInput.into())
+        );
+        
+        // The batch quota is separate from the single-issue quota
+        let issue_single = |contract: &mut CasperCredIQHostRef, n: u64| {
+            let input = input_for(contract, n);
+            contract.try_issue_credential(
+                input.issuer_did,
+                input.holder_did,
+                holder,
+                input.credential_hash,
+                input.issuer_signature,
+                input.ipfs_hash,
+                input.ai_assessment_id,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        for n in 1000..1025 {
+            assert!(issue_single(&mut contract, n).is_ok());
+        }
+        assert_eq!(issue_single(&mut contract, 1025), Err(Error::RateLimitExceeded.into()));
+        for batch in 0..4 {
+            let start = 10 + batch * MAX_BATCH_SIZE as u64;
+            let inputs = inputs_for(&mut contract, start..start + MAX_BATCH_SIZE as u64);
+            contract.issue_credentials_batch(inputs);
+        }
+        let inputs = inputs_for(&mut contract, 2000..2098);
+        assert_eq!(
+            contract.try_issue_credentials_batch(inputs),
+            Err(Error::RateLimitExceeded.into())
+        );
+        
+        env.advance_block_time(60 * 60 * 1000 + 1);
+        let inputs = inputs_for(&mut contract, 2000..2002);
+        assert_eq!(contract.issue_credentials_batch(inputs).len(), 2);
+    }
+
+    #[test]
+    fn test_schema_registry() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let stranger = env.get_account(2);
+        let holder = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        
+        env.set_caller(issuer);
+        let v1 = register_test_schema(&mut contract);
+        let v2 = contract.register_schema(
+            "EmployeeCredential".to_string(),
+            "2.0.0".to_string(),
+            "d".repeat(64),
+            valid_ipfs_hash(),
+        );
+        assert_eq!((v1, v2), (0, 1));
+        assert_eq!(
+            contract.try_register_schema(
+                "EmployeeCredential".to_string(),
+                "1.0.0".to_string(),
+                "e".repeat(64),
+                valid_ipfs_hash(),
+            ),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        env.set_caller(stranger);
+        assert_eq!(
+            contract.try_register_schema(
+                "Fake".to_string(),
+                "1.0.0".to_string(),
+                "e".repeat(64),
+                valid_ipfs_hash(),
+            ),
+            Err(Error::NotAuthorized.into())
+        );
+        assert_eq!(contract.try_deprecate_schema(v1), Err(Error::NotAuthorized.into()));
+        
+        let issue_with = |contract: &mut CasperCredIQHostRef, schema_id: u32| {
+            let credential_hash = numbered_credential_hash(contract.get_total_credentials().as_u64());
+            let assessment = assess(&env, contract, &credential_hash, 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                credential_hash.clone(),
+                sign_hash(&env, issuer, &credential_hash),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        env.set_caller(issuer);
+        let id = issue_with(&mut contract, v1).unwrap();
+        assert_eq!(contract.get_credential(id).unwrap().schema_id, v1);
+        assert_eq!(issue_with(&mut contract, 7), Err(Error::SchemaNotFound.into()));
+        
+        // Deprecated schemas keep their credentials but take no new ones
+        contract.deprecate_schema(v1);
+        assert_eq!(issue_with(&mut contract, v1), Err(Error::SchemaDeprecated.into()));
+        assert!(issue_with(&mut contract, v2).is_ok());
+        assert!(contract.verify_credential(id));
+        
+        assert_eq!(contract.get_schema_credential_count(v1), 1);
+        assert_eq!(contract.get_schema_credential_count(v2), 1);
+        
+        let schemas = contract.list_schemas(0, 10);
+        assert_eq!(contract.get_schema_count(), 2);
+        assert_eq!(schemas.len(), 2);
+        assert!(schemas[0].deprecated);
+        assert_eq!(schemas[1].version, "2.0.0");
+        assert_eq!(contract.list_schemas(1, 10).len(), 1);
+        assert!(contract.list_schemas(5, 10).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_credential_hash() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue_hash = |contract: &mut CasperCredIQHostRef, credential_hash: String| {
+            let assessment = assess(&env, contract, &credential_hash, 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                credential_hash.clone(),
+                sign_hash(&env, issuer, &credential_hash),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        let id = issue_hash(&mut contract, valid_credential_hash()).unwrap();
+        assert_eq!(contract.get_credential_id_by_hash(valid_credential_hash()), Some(id));
+        assert_eq!(contract.get_credential_id_by_hash(valid_credential_hash().to_uppercase()), Some(id));
+        assert_eq!(contract.get_credential_id_by_hash(numbered_credential_hash(1)), None);
+        
+        assert_eq!(issue_hash(&mut contract, valid_credential_hash()), Err(Error::AlreadyExists.into()));
+        assert_eq!(
+            issue_hash(&mut contract, valid_credential_hash().to_uppercase()),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        // Revoking does not free the hash
+        contract.revoke_credential(id, "Test".to_string());
+        assert_eq!(issue_hash(&mut contract, valid_credential_hash()), Err(Error::AlreadyExists.into()));
+        
+        // Duplicates inside one batch are rejected as well
+        let input = CredentialInput {
+            issuer_did: "did:casper:issuer".to_string(),
+            holder_did: account_did(&holder),
+            holder_address: holder,
+            credential_hash: numbered_credential_hash(1),
+            issuer_signature: sign_hash(&env, issuer, &numbered_credential_hash(1)),
+            ipfs_hash: valid_ipfs_hash(),
+            ai_assessment_id: assess(&env, &mut contract, &numbered_credential_hash(1), 90),
+            expires_in_days: 365,
+            schema_id,
+            valid_from: None,
+            expires_at: None,
+            ai_provenance: None,
+            claims: Vec::new(),
+            claims_root: None,
+            required_endorsements: 0,
+        };
+        assert_eq!(
+            contract.try_issue_credentials_batch(vec![input.clone(), input]),
+            Err(Error::AlreadyExists.into())
+        );
+        assert_eq!(contract.get_total_credentials(), U256::one());
     }
 
-    pub fn get_holder_credential_at_index(&self, holder: Address, index: u32) -> Option<U256> {
-        self.holder_credentials.get(&(holder, index))
+    #[test]
+    fn test_validity_window() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        let verifier = env.get_account(2);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue_window = |contract: &mut CasperCredIQHostRef, n: u64, valid_from: u64, expires_at: u64| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.try_issue_credential_with_validity(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                valid_from,
+                expires_at,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        let now = env.block_time();
+        let month = 30 * DAY_MS;
+        let id = issue_window(&mut contract, 1, now + month, now + 2 * month).unwrap();
+        
+        // Not yet valid
+        assert!(!contract.verify_credential(id));
+        assert!(contract.get_credential(id).is_none());
+        env.set_caller(verifier);
+        assert!(!contract.verify_credential_cryptographic(id, numbered_credential_hash(1), "FULL".to_string()));
+        
+        env.advance_block_time(month);
+        assert!(contract.verify_credential(id));
+        assert!(contract.verify_credential_cryptographic(id, numbered_credential_hash(1), "FULL".to_string()));
+        env.set_caller(issuer);
+        let vc = contract.get_credential(id).unwrap();
+        assert_eq!((vc.valid_from, vc.expires_at), (now + month, now + 2 * month));
+        
+        // Expires at the exact instant given
+        env.advance_block_time(month - 1);
+        assert!(contract.verify_credential(id));
+        env.advance_block_time(1);
+        assert!(!contract.verify_credential(id));
+        
+        // Empty, inverted or already-ended windows are rejected
+        let now = env.block_time();
+        assert_eq!(issue_window(&mut contract, 2, now + month, now + month), Err(Error::InvalidInput.into()));
+        assert_eq!(issue_window(&mut contract, 2, now + month, now + 1), Err(Error::InvalidInput.into()));
+        assert_eq!(issue_window(&mut contract, 2, 0, now), Err(Error::InvalidInput.into()));
+        assert!(issue_window(&mut contract, 2, 0, u64::MAX).is_ok());
+        
+        // Relative expiry overflow reverts instead of wrapping
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(3), 90);
+        assert_eq!(
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(3),
+                sign_hash(&env, issuer, &numbered_credential_hash(3)),
+                valid_ipfs_hash(),
+                assessment,
+                u64::MAX,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            ),
+            Err(Error::InvalidInput.into())
+        );
     }
 
-    pub fn get_issuer_credential_count(&self, issuer: Address) -> u32 {
-        self.issuer_count.get(&issuer).unwrap_or(0)
+    #[test]
+    fn test_credential_renewal() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        env.set_caller(issuer);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 90);
+        let id = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            valid_credential_hash(),
+            valid_signature(&env, issuer),
+            valid_ipfs_hash(),
+            assessment,
+            30,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        let old_expires_at = contract.get_credential(id).unwrap().expires_at;
+        
+        // Renewal works after expiry and keeps the id and its history
+        env.advance_block_time(31 * DAY_MS);
+        assert!(!contract.verify_credential(id));
+        let new_expires_at = env.block_time() + 90 * DAY_MS;
+        contract.renew_credential(id, new_expires_at);
+        assert!(contract.verify_credential(id));
+        assert_eq!(contract.get_credential(id).unwrap().expires_at, new_expires_at);
+        assert!(env.emitted_event(
+            &contract,
+            CredentialRenewed {
+                credential_id: id,
+                renewed_by: issuer,
+                old_expires_at,
+                new_expires_at,
+                timestamp: env.block_time(),
+            }
+        ));
+        assert_eq!(contract.get_audit_count(id), 2);
+        assert_eq!(contract.get_audit_log_at_index(id, 1).unwrap().action, "RENEWED");
+        assert_eq!(contract.get_holder_credential_count(holder), 1);
+        
+        // Must extend, and stay within the maximum renewal length
+        assert_eq!(contract.try_renew_credential(id, new_expires_at), Err(Error::InvalidInput.into()));
+        assert_eq!(
+            contract.try_renew_credential(id, env.block_time() + 366 * DAY_MS),
+            Err(Error::InvalidInput.into())
+        );
+        
+        env.set_caller(holder);
+        assert_eq!(
+            contract.try_renew_credential(id, new_expires_at + DAY_MS),
+            Err(Error::NotAuthorized.into())
+        );
+        assert_eq!(contract.try_set_max_renewal_period(0), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(owner);
+        contract.pause();
+        assert_eq!(contract.try_set_max_renewal_period(400 * DAY_MS), Err(Error::ContractPaused.into()));
+        contract.unpause();
+        contract.set_max_renewal_period(400 * DAY_MS);
+        assert_eq!(contract.get_max_renewal_period(), 400 * DAY_MS);
+        contract.renew_credential(id, env.block_time() + 366 * DAY_MS);
+        
+        contract.revoke_credential(id, "Test".to_string());
+        assert_eq!(
+            contract.try_renew_credential(id, env.block_time() + 367 * DAY_MS),
+            Err(Error::RevokedCredential.into())
+        );
+        assert_eq!(contract.try_renew_credential(U256::from(9), new_expires_at), Err(Error::CredentialNotFound.into()));
     }
 
-    pub fn get_issuer_credential_at_index(&self, issuer: Address, index: u32) -> Option<U256> {
-        self.issuer_credentials.get(&(issuer, index))
+    #[test]
+    fn test_credential_supersession() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        let other_holder = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let supersede = |contract: &mut CasperCredIQHostRef, old_id: U256, n: u64, holder_address: Address| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.try_supersede_credential(
+                old_id,
+                "did:casper:issuer".to_string(),
+                account_did(&holder_address),
+                holder_address,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        env.set_caller(issuer);
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(0), 90);
+        let first = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            numbered_credential_hash(0),
+            sign_hash(&env, issuer, &numbered_credential_hash(0)),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        
+        let second = supersede(&mut contract, first, 1, holder).unwrap();
+        let third = supersede(&mut contract, second, 2, holder).unwrap();
+        
+        assert!(contract.is_revoked(first));
+        assert!(contract.is_revoked(second));
+        assert!(contract.verify_credential(third));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialRevoked {
+                credential_id: first,
+                revoked_by: issuer,
+                reason: "superseded".to_string(),
+                timestamp: env.block_time(),
+                was_already_revoked: false,
+            }
+        ));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialSuperseded {
+                old_credential_id: second,
+                new_credential_id: third,
+                superseded_by: issuer,
+                timestamp: env.block_time(),
+            }
+        ));
+        
+        let vc = contract.get_credential(second).unwrap();
+        assert_eq!((vc.supersedes, vc.superseded_by), (Some(first), Some(third)));
+        assert_eq!(contract.get_latest_credential_id(first), Some(third));
+        assert_eq!(contract.get_latest_credential_id(third), Some(third));
+        assert_eq!(contract.get_latest_credential_id(U256::from(99)), None);
+        
+        // Revoked credentials cannot be superseded; nor can the holder change
+        assert_eq!(supersede(&mut contract, first, 3, holder), Err(Error::RevokedCredential.into()));
+        assert_eq!(supersede(&mut contract, third, 3, other_holder), Err(Error::InvalidInput.into()));
+        
+        // A failed replacement leaves the old credential untouched
+        assert_eq!(supersede(&mut contract, third, 2, holder), Err(Error::AlreadyExists.into()));
+        assert!(contract.verify_credential(third));
+        
+        env.set_caller(holder);
+        assert_eq!(supersede(&mut contract, third, 3, holder), Err(Error::NotAuthorized.into()));
     }
 
-    // ================ AUDIT LOG FUNCTIONS ================
+    #[test]
+    fn test_holder_acceptance() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        let stranger = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue = |contract: &mut CasperCredIQHostRef, n: u64| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        env.set_caller(issuer);
+        assert!(!contract.is_acceptance_required(issuer));
+        contract.set_acceptance_required(true);
+        assert!(contract.is_acceptance_required(issuer));
+        
+        let accepted = issue(&mut contract, 1);
+        let rejected = issue(&mut contract, 2);
+        assert_eq!(contract.get_credential(accepted).unwrap().status, CredentialStatus::Pending);
+        assert!(!contract.verify_credential(accepted));
+        assert!(!contract.verify_credential_cryptographic(accepted, numbered_credential_hash(1), "FULL".to_string()));
+        assert_eq!(contract.get_holder_credential_count(holder), 0);
+        
+        // Only the holder can respond
+        env.set_caller(stranger);
+        assert_eq!(contract.try_accept_credential(accepted), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(holder);
+        contract.accept_credential(accepted);
+        contract.reject_credential(rejected);
+        assert!(env.emitted_event(
+            &contract,
+            CredentialAccepted { credential_id: accepted, holder, timestamp: env.block_time() }
+        ));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialRejected { credential_id: rejected, holder, timestamp: env.block_time() }
+        ));
+        
+        assert!(contract.verify_credential(accepted));
+        assert!(!contract.verify_credential(rejected));
+        assert_eq!(contract.get_credential(rejected).unwrap().status, CredentialStatus::Rejected);
+        assert!(status_bit_set(&contract, rejected));
+        assert!(!status_bit_set(&contract, accepted));
+        assert!(contract.is_revoked(rejected));
+        assert!(!contract.is_revoked(accepted));
+        assert_eq!(contract.get_holder_credential_count(holder), 1);
+        assert_eq!(contract.get_holder_credential_at_index(holder, 0), Some(accepted));
+        assert_eq!(contract.get_audit_log_at_index(accepted, 1).unwrap().action, "ACCEPTED");
+        
+        // Responses are final
+        assert_eq!(contract.try_accept_credential(rejected), Err(Error::InvalidInput.into()));
+        assert_eq!(contract.try_reject_credential(accepted), Err(Error::InvalidInput.into()));
+        
+        // Without the setting, credentials are active straight away
+        env.set_caller(issuer);
+        contract.set_acceptance_required(false);
+        let direct = issue(&mut contract, 3);
+        assert!(contract.verify_credential(direct));
+        assert_eq!(contract.get_holder_credential_count(holder), 2);
+    }
 
-    pub fn get_audit_count(&self, credential_id: U256) -> u32 {
-        self.audit_count.get(&credential_id).unwrap_or(0)
+    #[test]
+    fn test_supersession_waits_for_acceptance() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(0), 90);
+        let original = contract.issue_credential(
+            "did:casper:issuer".to_string(),
+            account_did(&holder),
+            holder,
+            numbered_credential_hash(0),
+            sign_hash(&env, issuer, &numbered_credential_hash(0)),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        let supersede = |contract: &mut CasperCredIQHostRef, n: u64| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.supersede_credential(
+                original,
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        contract.set_acceptance_required(true);
+        let declined = supersede(&mut contract, 1);
+        
+        // The original stays valid while its replacement is pending...
+        assert!(contract.verify_credential(original));
+        assert!(!contract.verify_credential(declined));
+        assert_eq!(contract.get_latest_credential_id(original), Some(original));
+        
+        // ...and after the holder declines it
+        env.set_caller(holder);
+        contract.reject_credential(declined);
+        assert!(contract.verify_credential(original));
+        
+        env.set_caller(issuer);
+        let replacement = supersede(&mut contract, 2);
+        let rival = supersede(&mut contract, 3);
+        env.set_caller(holder);
+        contract.accept_credential(replacement);
+        
+        // A second pending replacement accepted later no longer claims the
+        // original, which keeps pointing at the first one
+        contract.accept_credential(rival);
+        assert_eq!(contract.get_credential(rival).unwrap().supersedes, None);
+        assert_eq!(contract.get_credential(replacement).unwrap().supersedes, Some(original));
+        
+        assert!(!contract.verify_credential(original));
+        assert!(contract.verify_credential(replacement));
+        assert_eq!(contract.get_latest_credential_id(original), Some(replacement));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialSuperseded {
+                old_credential_id: original,
+                new_credential_id: replacement,
+                superseded_by: issuer,
+                timestamp: env.block_time(),
+            }
+        ));
+    }
+
+    #[test]
+    fn test_request_queue() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        let stranger = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let submit = |contract: &mut CasperCredIQHostRef, issuer_did: &str| {
+            contract.try_submit_request(
+                issuer_did.to_string(),
+                account_did(&holder),
+                "employee".to_string(),
+                valid_ipfs_hash(),
+            )
+        };
+        
+        env.set_caller(holder);
+        let ids: Vec<u32> = (0..3).map(|_| submit(&mut contract, "did:casper:issuer").unwrap()).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(submit(&mut contract, "did:casper:unknown"), Err(Error::InvalidDID.into()));
+        
+        // The holder DID must be registered and belong to the requester
+        for holder_did in ["did:casper:unknown".to_string(), account_did(&stranger)] {
+            assert_eq!(
+                contract.try_submit_request(
+                    "did:casper:issuer".to_string(),
+                    holder_did,
+                    "employee".to_string(),
+                    valid_ipfs_hash(),
+                ),
+                Err(Error::InvalidDID.into())
+            );
+        }
+        
+        let issuer_did = "did:casper:issuer".to_string();
+        assert_eq!(contract.get_pending_request_count(issuer_did.clone()), 3);
+        assert_eq!(contract.get_pending_requests(issuer_did.clone(), 0, 2).len(), 2);
+        assert_eq!(contract.get_pending_requests(issuer_did.clone(), 2, 2)[0].request_id, 2);
+        
+        // Approval issues through the normal checks, to the requester
+        env.set_caller(issuer);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 90);
+        let credential_id = contract.approve_request(
+            1,
+            valid_credential_hash(),
+            valid_signature(&env, issuer),
+            valid_ipfs_hash(),
+            assessment,
+            365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+        );
+        let vc = contract.get_credential(credential_id).unwrap();
+        assert_eq!(vc.holder_address, holder);
+        assert_eq!(vc.holder_did, account_did(&holder));
+        
+        let request = contract.get_request(1).unwrap();
+        assert_eq!(request.status, RequestStatus::Approved);
+        assert_eq!(request.credential_id, Some(credential_id));
+        
+        env.set_caller(stranger);
+        assert_eq!(contract.try_reject_request(0, "No".to_string()), Err(Error::NotAuthorized.into()));
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(1), 90);
+        assert_eq!(
+            contract.try_approve_request(
+                0,
+                numbered_credential_hash(1),
+                sign_hash(&env, stranger, &numbered_credential_hash(1)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            ),
+            Err(Error::NotAuthorized.into())
+        );
+        
+        env.set_caller(issuer);
+        contract.reject_request(0, "Insufficient evidence".to_string());
+        assert_eq!(contract.get_request(0).unwrap().rejection_reason, "Insufficient evidence");
+        assert_eq!(contract.try_reject_request(0, "Again".to_string()), Err(Error::InvalidInput.into()));
+        assert_eq!(contract.try_reject_request(9, "No".to_string()), Err(Error::RequestNotFound.into()));
+        
+        let pending = contract.get_pending_requests(issuer_did.clone(), 0, 10);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].request_id, 2);
+        
+        // Requesters can only keep a bounded number of requests open
+        env.set_caller(holder);
+        for _ in 0..9 {
+            submit(&mut contract, "did:casper:issuer").unwrap();
+        }
+        assert_eq!(submit(&mut contract, "did:casper:issuer"), Err(Error::RateLimitExceeded.into()));
+        assert_eq!(contract.get_pending_request_count(issuer_did), 10);
+    }
+
+    #[test]
+    fn test_confidence_review() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        let reviewer = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        contract.grant_role(reviewer, Role::Reviewer, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue = |contract: &mut CasperCredIQHostRef, n: u64, ai_confidence: u8| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), ai_confidence);
+            contract.issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        contract.set_min_confidence(70);
+        assert_eq!(contract.try_set_min_confidence(101), Err(Error::InvalidInput.into()));
+        
+        env.set_caller(issuer);
+        assert_eq!(contract.try_set_min_confidence(0), Err(Error::NotAuthorized.into()));
+        let confident = issue(&mut contract, 1, 70);
+        let low = issue(&mut contract, 2, 3);
+        let rejected = issue(&mut contract, 3, 10);
+        assert!(contract.verify_credential(confident));
+        assert!(!contract.verify_credential(low));
+        assert_eq!(contract.get_credential(low).unwrap().status, CredentialStatus::NeedsReview);
+        assert_eq!(contract.get_holder_credential_count(holder), 1);
+        
+        // Only reviewers, and never the issuer itself
+        assert_eq!(contract.try_approve_review(low), Err(Error::NotAuthorized.into()));
+        env.set_caller(owner);
+        contract.grant_role(issuer, Role::Reviewer, Maybe::None);
+        env.set_caller(issuer);
+        assert_eq!(contract.try_approve_review(low), Err(Error::NotAuthorized.into()));
+        
+        env.set_caller(reviewer);
+        assert_eq!(contract.try_approve_review(confident), Err(Error::InvalidInput.into()));
+        contract.approve_review(low);
+        contract.reject_review(rejected, "Evidence does not match".to_string());
+        assert!(contract.verify_credential(low));
+        assert!(!contract.verify_credential(rejected));
+        assert!(status_bit_set(&contract, rejected));
+        assert!(!status_bit_set(&contract, low));
+        assert!(contract.is_revoked(rejected));
+        assert!(!contract.is_revoked(low));
+        assert_eq!(contract.get_holder_credential_count(holder), 2);
+        assert!(env.emitted_event(
+            &contract,
+            CredentialReviewed { credential_id: low, reviewer, approved: true, timestamp: env.block_time() }
+        ));
+        let log = contract.get_audit_log_at_index(rejected, 1).unwrap();
+        assert_eq!((log.action.as_str(), log.actor), ("REVIEW_REJECTED", reviewer));
+        assert_eq!(log.details, "Evidence does not match");
+        
+        // Per-issuer thresholds override the global one until cleared
+        env.set_caller(owner);
+        contract.set_issuer_min_confidence(issuer, Some(95));
+        assert_eq!(contract.get_min_confidence(issuer), 95);
+        assert_eq!(contract.get_min_confidence(holder), 70);
+        env.set_caller(issuer);
+        let strict = issue(&mut contract, 4, 90);
+        assert!(!contract.verify_credential(strict));
+        
+        env.set_caller(owner);
+        contract.set_issuer_min_confidence(issuer, None);
+        env.set_caller(issuer);
+        contract.set_acceptance_required(true);
+        let relaxed = issue(&mut contract, 5, 90);
+        assert_eq!(contract.get_credential(relaxed).unwrap().status, CredentialStatus::Pending);
+        
+        // Reviewed credentials still go through holder acceptance when required
+        env.set_caller(reviewer);
+        contract.approve_review(strict);
+        env.set_caller(issuer);
+        assert_eq!(contract.get_credential(strict).unwrap().status, CredentialStatus::Pending);
+    }
+
+    #[test]
+    fn test_ai_provenance() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let provenance = |recommendation: AiRecommendation| AiProvenance {
+            model_id: "gemini-1.5-pro".to_string(),
+            model_version: "2024-09".to_string(),
+            assessment_hash: "e".repeat(64),
+            recommendation,
+        };
+        let issue = |contract: &mut CasperCredIQHostRef, n: u64, ai_provenance: AiProvenance| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 92);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::Some(ai_provenance),
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        let id = issue(&mut contract, 1, provenance(AiRecommendation::Approve)).unwrap();
+        assert_eq!(contract.get_credential(id).unwrap().ai_provenance, Some(provenance(AiRecommendation::Approve)));
+        assert!(contract.verify_credential(id));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialIssued {
+                credential_id: id,
+                holder,
+                issuer,
+                issuer_did: "did:casper:issuer".to_string(),
+                holder_did: account_did(&holder),
+                ai_confidence: 92,
+                credential_hash: numbered_credential_hash(1),
+                ipfs_hash: valid_ipfs_hash(),
+                timestamp: env.block_time(),
+                schema_id,
+                ai_model_id: "gemini-1.5-pro".to_string(),
+                ai_model_version: "2024-09".to_string(),
+                ai_assessment_hash: "e".repeat(64),
+                ai_recommendation: Some(AiRecommendation::Approve),
+                ai_assessment_id: contract.get_credential(id).unwrap().ai_assessment_id,
+            }
+        ));
+        
+        // A REVIEW or REJECT recommendation holds the credential for review
+        let flagged = issue(&mut contract, 2, provenance(AiRecommendation::Reject)).unwrap();
+        assert_eq!(contract.get_credential(flagged).unwrap().status, CredentialStatus::NeedsReview);
+        
+        let mut malformed = provenance(AiRecommendation::Approve);
+        malformed.assessment_hash = "not-a-hash".to_string();
+        assert_eq!(issue(&mut contract, 3, malformed), Err(Error::InvalidInput.into()));
+        let mut unnamed = provenance(AiRecommendation::Approve);
+        unnamed.model_id = String::new();
+        assert_eq!(issue(&mut contract, 3, unnamed), Err(Error::InvalidInput.into()));
+    }
+
+    #[test]
+    fn test_ai_oracle_assessments() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(2);
+        let oracle = test_oracle(&env);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let issue = |contract: &mut CasperCredIQHostRef, n: u64, ai_assessment_id: u32| {
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                ai_assessment_id,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        // Only AI oracles can post assessments
+        env.set_caller(issuer);
+        assert_eq!(
+            contract.try_submit_ai_assessment(numbered_credential_hash(1), 90, "e".repeat(64)),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(oracle);
+        assert_eq!(
+            contract.try_submit_ai_assessment(numbered_credential_hash(1), 101, "e".repeat(64)),
+            Err(Error::InvalidInput.into())
+        );
+        
+        env.set_caller(issuer);
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(1), 88);
+        let id = issue(&mut contract, 1, assessment).unwrap();
+        assert_eq!(contract.get_credential(id).unwrap().ai_confidence, 88);
+        assert_eq!(contract.get_ai_assessment(assessment).unwrap().used_for, Some(id));
+        
+        // An assessment backs exactly one credential
+        assert_eq!(issue(&mut contract, 2, assessment), Err(Error::AssessmentAlreadyUsed.into()));
+        
+        // Unknown ids and assessments of other hashes are rejected
+        assert_eq!(issue(&mut contract, 2, 999), Err(Error::AssessmentNotFound.into()));
+        let other = assess(&env, &mut contract, &numbered_credential_hash(3), 95);
+        assert_eq!(issue(&mut contract, 2, other), Err(Error::HashMismatch.into()));
+        
+        // Issuers cannot act as their own oracle
+        env.set_caller(owner);
+        contract.grant_role(issuer, Role::AiOracle, Maybe::None);
+        env.set_caller(issuer);
+        let own = contract.submit_ai_assessment(numbered_credential_hash(2), 100, "e".repeat(64));
+        assert_eq!(issue(&mut contract, 2, own), Err(Error::NotAuthorized.into()));
+        
+        // Assessments stop counting once the oracle loses its role
+        let stale = assess(&env, &mut contract, &numbered_credential_hash(2), 90);
+        env.set_caller(owner);
+        contract.revoke_role(oracle, Role::AiOracle);
+        env.set_caller(issuer);
+        assert_eq!(issue(&mut contract, 2, stale), Err(Error::NotAuthorized.into()));
+        assert!(contract.get_ai_assessment(stale).unwrap().used_for.is_none());
+    }
+
+    #[test]
+    fn test_endorsements() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let partner = env.get_account(2);
+        let auditor = env.get_account(3);
+        let holder = env.get_account(4);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        for (endorser, did) in [(partner, "did:casper:partner"), (auditor, "did:casper:auditor")] {
+            contract.set_access_level(endorser, 2, Maybe::None);
+            contract.register_issuer(did.to_string(), endorser, "Partner".to_string(), valid_ipfs_hash());
+            contract.add_issuer_key(did.to_string(), env.public_key(&endorser));
+            create_test_did(&env, &mut contract, did, endorser);
+        }
+        contract.set_access_level(issuer, 2, Maybe::None);
+        
+        env.set_caller(issuer);
+        let assessment = assess(&env, &mut contract, &valid_credential_hash(), 90);
+        let issue = |contract: &mut CasperCredIQHostRef, required: u32| {
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                valid_credential_hash(),
+                valid_signature(&env, issuer),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::Some(required),
+            )
+        };
+        assert_eq!(issue(&mut contract, MAX_ENDORSEMENTS + 1), Err(Error::InvalidInput.into()));
+        let id = issue(&mut contract, 2).unwrap();
+        
+        // The requirement applies from issuance
+        assert_eq!(contract.get_required_endorsements(id), 2);
+        assert!(!contract.verify_credential(id));
+        
+        // Only the issuing address or the owner changes the requirement
+        env.set_caller(partner);
+        assert_eq!(contract.try_set_required_endorsements(id, 3), Err(Error::NotAuthorized.into()));
+        env.set_caller(issuer);
+        assert_eq!(
+            contract.try_set_required_endorsements(id, MAX_ENDORSEMENTS + 1),
+            Err(Error::InvalidInput.into())
+        );
+        contract.set_required_endorsements(id, 3);
+        assert_eq!(contract.get_required_endorsements(id), 3);
+        
+        // Lowering it again is left to the owner
+        assert_eq!(contract.try_set_required_endorsements(id, 0), Err(Error::NotOwner.into()));
+        env.set_caller(owner);
+        contract.set_required_endorsements(id, 1);
+        assert_eq!(contract.get_required_endorsements(id), 1);
+        env.set_caller(issuer);
+        contract.set_required_endorsements(id, 2);
+        assert!(!contract.verify_credential(id));
+        
+        // The issuer cannot endorse its own credential
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:issuer".to_string(), valid_signature(&env, issuer)),
+            Err(Error::NotAuthorized.into())
+        );
+        
+        env.set_caller(partner);
+        contract.endorse_credential(id, "did:casper:partner".to_string(), valid_signature(&env, partner));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialEndorsed {
+                credential_id: id,
+                endorser: partner,
+                endorser_did: "did:casper:partner".to_string(),
+                endorsement_count: 1,
+                timestamp: env.block_time(),
+            }
+        ));
+        assert!(!contract.verify_credential(id));
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:partner".to_string(), valid_signature(&env, partner)),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        // Endorsers sign with a key of the DID they control
+        env.set_caller(auditor);
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:auditor".to_string(), valid_signature(&env, partner)),
+            Err(Error::InvalidSignature.into())
+        );
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:partner".to_string(), valid_signature(&env, auditor)),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(holder);
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:auditor".to_string(), valid_signature(&env, auditor)),
+            Err(Error::NotAuthorized.into())
+        );
+        
+        env.set_caller(auditor);
+        contract.endorse_credential(id, "did:casper:auditor".to_string(), valid_signature(&env, auditor));
+        assert!(contract.verify_credential(id));
+        assert_eq!(
+            contract.get_endorsements(id).iter().map(|e| e.endorser).collect::<Vec<_>>(),
+            vec![partner, auditor]
+        );
+        
+        env.set_caller(issuer);
+        contract.revoke_credential(id, "Test".to_string());
+        env.set_caller(partner);
+        assert_eq!(
+            contract.try_endorse_credential(id, "did:casper:partner".to_string(), valid_signature(&env, partner)),
+            Err(Error::RevokedCredential.into())
+        );
+    }
+
+    #[test]
+    fn test_holder_rotation() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let partner = env.get_account(2);
+        let holder = env.get_account(3);
+        let recovered = env.get_account(4);
+        let fresh = env.get_account(5);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        contract.register_issuer("did:casper:partner".to_string(), partner, "Partner".to_string(), valid_ipfs_hash());
+        contract.add_issuer_key("did:casper:partner".to_string(), env.public_key(&partner));
+        create_test_did(&env, &mut contract, "did:casper:partner", partner);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        contract.set_access_level(partner, 2, Maybe::None);
+        
+        let issue = |contract: &mut CasperCredIQHostRef, signer: Address, issuer_did: &str, n: u64| {
+            env.set_caller(signer);
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.issue_credential(
+                issuer_did.to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, signer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        let ours = issue(&mut contract, issuer, "did:casper:issuer", 1);
+        let theirs = issue(&mut contract, partner, "did:casper:partner", 2);
+        
+        env.set_caller(recovered);
+        assert_eq!(contract.try_rotate_holder(holder, recovered), Err(Error::NotAuthorized.into()));
+        
+        // An issuer only moves the credentials it issued
+        env.set_caller(issuer);
+        contract.rotate_holder(holder, recovered);
+        assert!(env.emitted_event(
+            &contract,
+            HolderRotated {
+                old_holder: holder,
+                new_holder: recovered,
+                rotated_by: issuer,
+                credential_count: 1,
+                timestamp: env.block_time(),
+            }
+        ));
+        assert_eq!(contract.get_holder_credential_count(holder), 1);
+        assert_eq!(contract.get_holder_credential_at_index(holder, 0), Some(theirs));
+        assert_eq!(contract.get_holder_credential_at_index(holder, 1), None);
+        assert_eq!(contract.get_holder_credential_at_index(recovered, 0), Some(ours));
+        assert_eq!(contract.get_credential(ours).unwrap().holder_address, recovered);
+        assert_eq!(contract.get_audit_log_at_index(ours, 1).unwrap().action, "HOLDER_ROTATED");
+        assert_eq!(contract.try_rotate_holder(holder, recovered), Err(Error::CredentialNotFound.into()));
+        
+        // Credentials waiting for acceptance move along with the rest
+        env.set_caller(partner);
+        contract.set_acceptance_required(true);
+        let waiting = issue(&mut contract, partner, "did:casper:partner", 3);
+        
+        // The holder moves the rest with a signature from the new key over
+        // the domain, this contract, the holder and its rotation nonce
+        let message = contract.get_holder_rotation_message(holder);
+        let mut expected = HOLDER_ROTATION_DOMAIN.to_vec();
+        expected.extend_from_slice(&contract.address().to_bytes().unwrap());
+        expected.extend_from_slice(&holder.to_bytes().unwrap());
+        expected.extend_from_slice(&0u64.to_le_bytes());
+        assert_eq!(message, Bytes::from(expected));
+        
+        let signature = encoding::encode_hex(&env.sign_message(&message, &fresh));
+        let wrong_signature = encoding::encode_hex(&env.sign_message(&message, &holder));
+        let bare_address = Bytes::from(holder.to_bytes().unwrap());
+        let bare_signature = encoding::encode_hex(&env.sign_message(&bare_address, &fresh));
+        env.set_caller(holder);
+        for rejected in [wrong_signature, bare_signature] {
+            assert_eq!(
+                contract.try_rotate_own_holder_address(env.public_key(&fresh), rejected),
+                Err(Error::InvalidSignature.into())
+            );
+        }
+        contract.rotate_own_holder_address(env.public_key(&fresh), signature.clone());
+        assert_eq!(contract.get_holder_rotation_nonce(holder), 1);
+        assert_eq!(contract.get_holder_credential_count(holder), 0);
+        assert_eq!(contract.get_holder_credential_at_index(fresh, 0), Some(theirs));
+        
+        // The signature is spent once the nonce moves on
+        assert_eq!(
+            contract.try_rotate_own_holder_address(env.public_key(&fresh), signature),
+            Err(Error::InvalidSignature.into())
+        );
+        
+        env.set_caller(fresh);
+        assert_eq!(contract.get_credential(waiting).unwrap().holder_address, fresh);
+        contract.accept_credential(waiting);
+        assert_eq!(contract.get_holder_credential_at_index(fresh, 1), Some(waiting));
+        
+        env.set_caller(owner);
+        assert_eq!(contract.try_rotate_holder(fresh, fresh), Err(Error::InvalidInput.into()));
+        contract.rotate_holder(fresh, recovered);
+        assert_eq!(contract.get_holder_credential_count(recovered), 3);
+        assert!(contract.verify_credential(theirs));
     }
 
-    pub fn get_audit_log_at_index(&self, credential_id: U256, index: u32) -> Option<AuditLog> {
-        self.audit_logs.get(&(credential_id, index))
+    #[test]
+    fn test_did_registry() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let holder = env.get_account(10);
+        let stranger = env.get_account(3);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        // An account creates its own DID, derived from its account hash
+        let alice = account_did(&holder);
+        assert_eq!(alice.len(), "did:casper:".len() + 64);
+        env.set_caller(stranger);
+        assert_eq!(
+            contract.try_create_did(alice.clone(), env.public_key(&stranger)),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(holder);
+        contract.create_did(alice.clone(), env.public_key(&holder));
+        assert_eq!(
+            contract.try_create_did(alice.clone(), env.public_key(&stranger)),
+            Err(Error::AlreadyExists.into())
+        );
+        assert_eq!(
+            contract.try_create_did("did:casper:alice".to_string(), env.public_key(&holder)),
+            Err(Error::NotAuthorized.into())
+        );
+        assert_eq!(
+            contract.try_create_did("did:web:alice.example".to_string(), env.public_key(&holder)),
+            Err(Error::NotAuthorized.into())
+        );
+        assert_eq!(
+            contract.try_create_did("did:casper:bad id".to_string(), env.public_key(&holder)),
+            Err(Error::DidInvalidIdentifier.into())
+        );
+        
+        // Only the controller updates the document
+        env.set_caller(stranger);
+        assert_eq!(
+            contract.try_rotate_did_key(alice.clone(), env.public_key(&stranger)),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(holder);
+        contract.rotate_did_key(alice.clone(), env.public_key(&stranger));
+        contract.set_did_service(
+            alice.clone(),
+            "profile".to_string(),
+            "LinkedDomains".to_string(),
+            "https://alice.example".to_string(),
+        );
+        contract.set_did_service(
+            alice.clone(),
+            "inbox".to_string(),
+            "DIDCommMessaging".to_string(),
+            "https://alice.example/inbox".to_string(),
+        );
+        contract.remove_did_service(alice.clone(), "profile".to_string());
+        
+        let document = contract.resolve_did(alice.clone()).unwrap();
+        assert_eq!(document.controller, holder);
+        assert_eq!(
+            document.verification_methods.iter().map(|m| (m.id.clone(), m.active)).collect::<Vec<_>>(),
+            vec![(format!("{}#key-1", alice), false), (format!("{}#key-2", alice), true)]
+        );
+        assert_eq!(document.verification_methods[1].public_key, env.public_key(&stranger));
+        assert_eq!(document.services.len(), 1);
+        assert_eq!(document.services[0].id, "inbox");
+        assert!(contract.resolve_did("did:casper:nobody".to_string()).is_none());
+        
+        // Rotation never runs out: the oldest rotated-out keys are dropped
+        for i in 11..20 {
+            contract.rotate_did_key(alice.clone(), env.public_key(&env.get_account(i)));
+        }
+        let methods = contract.resolve_did(alice.clone()).unwrap().verification_methods;
+        assert_eq!(methods.len(), 8);
+        assert_eq!(methods[0].id, format!("{}#key-4", alice));
+        assert_eq!(methods[7].id, format!("{}#key-11", alice));
+        assert_eq!(methods.iter().filter(|m| m.active).count(), 1);
+        assert_eq!(
+            contract.try_rotate_did_key(alice.clone(), env.public_key(&env.get_account(19))),
+            Err(Error::AlreadyExists.into())
+        );
+        
+        let issue_to = |contract: &mut CasperCredIQHostRef, holder_did: &str, n: u64| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                holder_did.to_string(),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
+        };
+        
+        env.set_caller(issuer);
+        assert!(issue_to(&mut contract, &alice, 1).is_ok());
+        assert_eq!(issue_to(&mut contract, "did:casper:nobody", 2), Err(Error::InvalidDID.into()));
+        
+        // The holder DID must belong to the holder address
+        assert_eq!(issue_to(&mut contract, &account_did(&stranger), 2), Err(Error::InvalidDID.into()));
+        
+        // External methods are accepted once an admin allows them
+        assert_eq!(issue_to(&mut contract, "did:web:alice.example", 2), Err(Error::InvalidDID.into()));
+        assert_eq!(
+            contract.try_set_external_did_method("web".to_string(), true),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(owner);
+        contract.set_external_did_method("web".to_string(), true);
+        assert_eq!(
+            contract.try_set_external_did_method("casper".to_string(), true),
+            Err(Error::InvalidInput.into())
+        );
+        assert_eq!(contract.get_external_did_methods(), vec!["web".to_string()]);
+        env.set_caller(issuer);
+        assert!(issue_to(&mut contract, "did:web:alice.example", 2).is_ok());
+        assert_eq!(issue_to(&mut contract, "did:web:", 3), Err(Error::DidInvalidIdentifier.into()));
+        
+        // Deactivated DIDs still resolve but are no longer accepted
+        env.set_caller(holder);
+        contract.deactivate_did(alice.clone());
+        assert!(contract.resolve_did(alice.clone()).unwrap().deactivated);
+        assert_eq!(
+            contract.try_rotate_did_key(alice.clone(), env.public_key(&holder)),
+            Err(Error::InvalidDID.into())
+        );
+        env.set_caller(issuer);
+        assert_eq!(issue_to(&mut contract, &alice, 3), Err(Error::InvalidDID.into()));
     }
 
-    // ================ GENERAL GETTERS ================
-
-    pub fn get_suspicious_activity_count(&self, address: Address) -> u32 {
-        self.suspicious_activity.get(&address).unwrap_or(0)
+    #[test]
+    fn test_issuer_did_binding() {
+        let env = odra_test::env();
+        let owner = env.get_account(0);
+        let issuer = env.get_account(1);
+        let squatter = env.get_account(2);
+        
+        env.set_caller(owner);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.register_issuer("did:casper:acme".to_string(), issuer, "Acme".to_string(), valid_ipfs_hash());
+        
+        // Nobody else can create a registered issuer's DID document...
+        env.set_caller(squatter);
+        assert_eq!(
+            contract.try_create_did("did:casper:acme".to_string(), env.public_key(&squatter)),
+            Err(Error::NotAuthorized.into())
+        );
+        
+        // ...an admin can, but it still belongs to the issuer's controller
+        env.set_caller(owner);
+        contract.create_did("did:casper:acme".to_string(), env.public_key(&issuer));
+        assert_eq!(contract.resolve_did("did:casper:acme".to_string()).unwrap().controller, issuer);
+        
+        // Nobody can claim an identifier ahead of its issuer, or another account's DID
+        env.set_caller(squatter);
+        assert_eq!(
+            contract.try_create_did("did:casper:globex".to_string(), env.public_key(&squatter)),
+            Err(Error::NotAuthorized.into())
+        );
+        assert_eq!(
+            contract.try_create_did(account_did(&issuer), env.public_key(&squatter)),
+            Err(Error::NotAuthorized.into())
+        );
+        env.set_caller(owner);
+        contract.register_issuer("did:casper:globex".to_string(), issuer, "Globex".to_string(), valid_ipfs_hash());
+        create_test_did(&env, &mut contract, "did:casper:globex", issuer);
+        assert_eq!(contract.resolve_did("did:casper:globex".to_string()).unwrap().controller, issuer);
+        
+        // An account DID can only be registered to the account it belongs to
+        create_test_did(&env, &mut contract, &account_did(&squatter), squatter);
+        assert_eq!(
+            contract.try_register_issuer(account_did(&squatter), issuer, "Squat".to_string(), valid_ipfs_hash()),
+            Err(Error::InvalidDID.into())
+        );
+        contract.register_issuer(account_did(&squatter), squatter, "Squatter".to_string(), valid_ipfs_hash());
     }
 
-    pub fn get_verification_count(&self, address: Address) -> u32 {
-        self.verification_data.get(&address)
-            .map(|vd| vd.verification_count)
-            .unwrap_or(0)
+    /// RFC 4648 base32, lowercase, unpadded (the inverse of the CID decoder).
+    fn encode_base32(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+        let mut out = String::new();
+        let (mut buffer, mut bits) = (0u32, 0);
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+        }
+        out
     }
 
-    pub fn get_owner(&self) -> Address {
-        self.owner.get().unwrap()
+    fn encode_base58(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+        let mut digits: Vec<u8> = Vec::new();
+        for &byte in bytes {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        core::iter::repeat('1')
+            .take(zeros)
+            .chain(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char))
+            .collect()
     }
 
-    pub fn get_access_level(&self, address: Address) -> u8 {
-        self.access_level.get(&address).unwrap_or(0)
+    #[test]
+    fn test_did_syntax() {
+        let did = validation::parse_did("did:casper:issuer").unwrap();
+        assert_eq!((did.method, did.identifier), ("casper", "issuer"));
+        let did = validation::parse_did("did:web:example.com:users:alice%20b").unwrap();
+        assert_eq!((did.method, did.identifier), ("web", "example.com:users:alice%20b"));
+        assert!(validation::parse_did("did:example:a::b").is_ok());
+        
+        assert_eq!(validation::parse_did("casper:issuer"), Err(DidError::MissingScheme));
+        assert_eq!(validation::parse_did("DID:casper:issuer"), Err(DidError::MissingScheme));
+        assert_eq!(validation::parse_did("did:casper"), Err(DidError::InvalidMethod));
+        assert_eq!(validation::parse_did("did::issuer"), Err(DidError::InvalidMethod));
+        assert_eq!(validation::parse_did("did:Casper:issuer"), Err(DidError::InvalidMethod));
+        assert_eq!(validation::parse_did("did:casper:"), Err(DidError::InvalidIdentifier));
+        assert_eq!(validation::parse_did("did:casper:issuer:"), Err(DidError::InvalidIdentifier));
+        assert_eq!(validation::parse_did("did:casper:a b"), Err(DidError::InvalidIdentifier));
+        assert_eq!(validation::parse_did("did:casper:a%2"), Err(DidError::InvalidIdentifier));
+        assert_eq!(validation::parse_did("did:casper:a%zz"), Err(DidError::InvalidIdentifier));
+        assert_eq!(validation::parse_did("did:casper:issuer#key-1"), Err(DidError::InvalidIdentifier));
     }
 
-    pub fn is_paused(&self) -> bool {
-        self.paused.get().unwrap_or(false)
-    }
-    
-    pub fn get_total_credentials(&self) -> U256 {
-        self.credential_counter.get().unwrap()
+    #[test]
+    fn test_cid_parsing() {
+        let v0 = validation::parse_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").unwrap();
+        assert_eq!((v0.version, v0.codec, v0.hash_code, v0.digest_len), (0, 0x70, 0x12, 32));
+        
+        let v1 = validation::parse_cid("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
+        assert_eq!((v1.version, v1.codec, v1.hash_code, v1.digest_len), (1, 0x70, 0x12, 32));
+        assert_eq!(
+            validation::parse_cid("BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"),
+            Ok(v1)
+        );
+        let hex = format!("f01701220{}", "ab".repeat(32));
+        assert_eq!(validation::parse_cid(&hex), Ok(v1));
+        
+        assert_eq!(validation::parse_cid(""), Err(CidError::Empty));
+        assert_eq!(
+            validation::parse_cid("QmXgqL8j5qN6U5K4z8XvY8T7S6D5F4G3H2J1K9L8M7N6B5V4C3"),
+            Err(CidError::UnsupportedMultibase)
+        );
+        assert_eq!(validation::parse_cid("ipfs://bafy"), Err(CidError::UnsupportedMultibase));
+        // '0' is outside the base58 alphabet
+        assert_eq!(
+            validation::parse_cid("Qm0wAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            Err(CidError::InvalidEncoding)
+        );
+        assert_eq!(validation::parse_cid("bafy1"), Err(CidError::InvalidEncoding));
+        assert_eq!(validation::parse_cid(&format!("f0270{}", "ab".repeat(34))), Err(CidError::UnsupportedVersion));
+        assert_eq!(validation::parse_cid(&format!("f01701220{}", "ab".repeat(31))), Err(CidError::InvalidMultihash));
+        assert_eq!(validation::parse_cid("f01701200"), Err(CidError::InvalidMultihash));
     }
 
-    // ================ INTERNAL HELPERS ================
-
-    fn check_not_paused(&self) {
-        if self.paused.get().unwrap_or(false) {
-            self.env().revert(Error::ContractPaused);
-        }
+    #[test]
+    fn test_hex_hash_parsing() {
+        assert_eq!(validation::parse_hex_hash(&"aB".repeat(32)), Ok([0xab; 32]));
+        assert_eq!(validation::parse_hex_hash(&"a".repeat(63)), Err(HashError::InvalidLength));
+        assert_eq!(validation::parse_hex_hash(&"g".repeat(64)), Err(HashError::NotHex));
+        // 64 bytes, but not 64 ASCII characters
+        assert_eq!(validation::parse_hex_hash(&"é".repeat(32)), Err(HashError::NotHex));
     }
 
-    fn can_view_credential(&self, credential_id: U256) -> bool {
-        let caller = self.env().caller();
-        let owner = self.owner.get().unwrap();
+    #[test]
+    fn test_input_validation_errors() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
         
-        if caller == owner {
-            return true;
-        }
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
         
-        let vc = match self.credentials.get(&credential_id) {
-            Some(v) => v,
-            None => return false,
+        let issue = |contract: &mut CasperCredIQHostRef, credential_hash: String, ipfs_hash: String, holder_did: &str| {
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                holder_did.to_string(),
+                holder,
+                credential_hash,
+                valid_signature(&env, issuer),
+                ipfs_hash,
+                0,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+                Maybe::None,
+            )
         };
         
-        if caller == vc.holder_address {
-            return true;
-        }
-        
-        if caller == vc.issuer_address {
-            return true;
-        }
+        assert_eq!(
+            issue(&mut contract, "z".repeat(64), valid_ipfs_hash(), &account_did(&holder)),
+            Err(Error::HashNotHex.into())
+        );
+        assert_eq!(
+            issue(&mut contract, valid_credential_hash(), "bafy".to_string(), &account_did(&holder)),
+            Err(Error::CidInvalidEncoding.into())
+        );
+        assert_eq!(
+            issue(&mut contract, valid_credential_hash(), valid_ipfs_hash(), "did:Casper:holder"),
+            Err(Error::DidInvalidMethod.into())
+        );
         
-        let caller_level = self.access_level.get(&caller).unwrap_or(0);
-        caller_level >= 3
+        assert_eq!(
+            contract.try_register_issuer(
+                "did:casper:other".to_string(),
+                issuer,
+                "Other".to_string(),
+                "not-a-cid".to_string(),
+            ),
+            Err(Error::CidUnsupportedMultibase.into())
+        );
+        assert_eq!(
+            contract.try_submit_request(
+                "did:casper:issuer".to_string(),
+                "did:casper:holder#key-1".to_string(),
+                "EmployeeCredential".to_string(),
+                valid_ipfs_hash(),
+            ),
+            Err(Error::DidInvalidIdentifier.into())
+        );
     }
 
-    fn check_rate_limit(&mut self, caller: Address, current_time: u64) {
-        let mut rl = self.rate_limit.get(&caller).unwrap_or(RateLimitData {
-            last_issue_time: 0,
-            issue_count: 0,
-        });
+    #[test]
+    fn test_credential_claims() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
         
-        let time_window = 60 * 60 * 1000;
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
         
-        // Reset if outside window
-        if current_time - rl.last_issue_time > time_window {
-            rl.issue_count = 0;
-            self.rate_limit.set(&caller, rl);
-            return;
-        }
+        let claim = |key: &str, value: &str| Claim { key: key.to_string(), value: value.to_string() };
+        let issue_with = |contract: &mut CasperCredIQHostRef, n: u64, claims: Vec<Claim>| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::Some(claims),
+                Maybe::None,
+                Maybe::None,
+            )
+        };
         
-        // Check limit
-        if rl.issue_count >= 25 {
-            self.log_suspicious_activity(caller, "Rate limit exceeded".to_string(), 4);
-            self.env().revert(Error::RateLimitExceeded);
+        let id = issue_with(&mut contract, 0, vec![claim("role", "admin"), claim("department", "finance")]).unwrap();
+        assert_eq!(contract.get_credential_claim(id, "role".to_string()), Some("admin".to_string()));
+        assert_eq!(contract.get_credential_claim(id, "department".to_string()), Some("finance".to_string()));
+        assert_eq!(contract.get_credential_claim(id, "clearance".to_string()), None);
+        assert_eq!(contract.get_credential_claim(U256::from(99), "role".to_string()), None);
+        
+        // Claims are readable by anyone, not only the credential's parties
+        env.set_caller(env.get_account(5));
+        assert_eq!(contract.get_credential_claim(id, "role".to_string()), Some("admin".to_string()));
+        env.set_caller(issuer);
+        
+        // Key count, key syntax, duplicate keys and value length are bounded
+        let too_many = (0..=claims::MAX_CLAIMS).map(|i| claim(&format!("key{}", i), "v")).collect();
+        let long_key = "k".repeat(claims::MAX_CLAIM_KEY_LEN + 1);
+        let long_value = "v".repeat(claims::MAX_CLAIM_VALUE_LEN + 1);
+        let invalid: Vec<Vec<Claim>> = vec![
+            too_many,
+            vec![claim("role", "admin"), claim("role", "viewer")],
+            vec![claim("Role", "admin")],
+            vec![claim("", "admin")],
+            vec![claim(&long_key, "admin")],
+            vec![claim("role", "")],
+            vec![claim("role", &long_value)],
+        ];
+        for claims in invalid {
+            assert_eq!(issue_with(&mut contract, 1, claims), Err(Error::InvalidInput.into()));
         }
-    }
-
-    fn log_suspicious_activity(&mut self, actor: Address, action: String, severity: u8) {
-        let current_count = self.suspicious_activity.get(&actor).unwrap_or(0);
-        self.suspicious_activity.set(&actor, current_count + 1);
         
-        self.env().emit_event(SuspiciousActivity {
-            actor,
-            action,
-            severity,
-            timestamp: self.env().get_block_time(),
-        });
+        let full = (0..claims::MAX_CLAIMS)
+            .map(|i| claim(&format!("key_{}", i), &"v".repeat(claims::MAX_CLAIM_VALUE_LEN)))
+            .collect();
+        let full_id = issue_with(&mut contract, 2, full).unwrap();
+        assert_eq!(contract.get_credential(full_id).unwrap().claims.len(), claims::MAX_CLAIMS);
+        
+        // A revoked credential no longer vouches for its claims
+        contract.revoke_credential(id, "Role changed".to_string());
+        assert_eq!(contract.get_credential_claim(id, "role".to_string()), None);
     }
 
-    fn add_audit_log(
-        &mut self,
-        credential_id: U256,
-        action: String,
-        actor: Address,
-        timestamp: u64,
-        details: String,
-    ) {
-        let count = self.audit_count.get(&credential_id).unwrap_or(0);
+    #[test]
+    fn test_selective_disclosure() {
+        let env = odra_test::env();
+        let issuer = env.get_account(0);
+        let holder = env.get_account(1);
+        let verifier = env.get_account(5);
         
-        let log = AuditLog {
-            action: action.clone(),
-            actor,
-            timestamp,
-            details,
+        env.set_caller(issuer);
+        let mut contract = CasperCredIQ::deploy(&env, NoArgs);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        
+        let claims = [
+            ("name", "Alice Example", [1u8; 32]),
+            ("role", "admin", [2u8; 32]),
+            ("department", "finance", [3u8; 32]),
+            ("clearance", "secret", [4u8; 32]),
+            ("employee_id", "E-1042", [5u8; 32]),
+        ];
+        let tree = merkle::ClaimTree::new(&claims).unwrap();
+        
+        let issue_with = |contract: &mut CasperCredIQHostRef, n: u64, claims_root: Maybe<String>| {
+            let assessment = assess(&env, contract, &numbered_credential_hash(n), 90);
+            contract.try_issue_credential(
+                "did:casper:issuer".to_string(),
+                account_did(&holder),
+                holder,
+                numbered_credential_hash(n),
+                sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                valid_ipfs_hash(),
+                assessment,
+                365,
+                schema_id,
+                Maybe::None,
+                Maybe::None,
+                claims_root,
+                Maybe::None,
+            )
         };
         
-        self.audit_logs.set(&(credential_id, count), log);
-        self.audit_count.set(&credential_id, count + 1);
+        assert_eq!(issue_with(&mut contract, 0, Maybe::Some("not-a-root".to_string())), Err(Error::InvalidInput.into()));
+        let id = issue_with(&mut contract, 0, Maybe::Some(tree.root_hex())).unwrap();
+        let without_root = issue_with(&mut contract, 1, Maybe::None).unwrap();
+        assert_eq!(contract.get_credential(id).unwrap().claims_root, Some(tree.root()));
         
-        self.env().emit_event(AuditLogCreated {
-            credential_id,
-            action,
-            actor,
-            timestamp,
-            audit_count: count + 1,
-        });
-    }
-
-    fn emit_verification_event(
-        &self,
-        credential_id: U256,
-        verifier: Address,
-        is_valid: bool,
-        verification_type: String,
-        timestamp: u64,
-    ) {
-        self.env().emit_event(CredentialVerified {
-            credential_id,
-            verifier,
-            is_valid,
-            verification_type,
-            timestamp,
-        });
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use odra::host::{Deployer, NoArgs};
-
-    fn valid_ipfs_hash() -> String {
-        "QmXgqL8j5qN6U5K4z8XvY8T7S6D5F4G3H2J1K9L8M7N6B5V4C3".to_string()
-    }
-    
-    fn valid_credential_hash() -> String {
-        "a".repeat(64)
-    }
-    
-    fn valid_signature() -> String {
-        "b".repeat(128)
+        // Every claim can be disclosed on its own
+        env.set_caller(verifier);
+        for (i, (key, value, salt)) in claims.iter().enumerate() {
+            let proof = tree.proof_hex(i).unwrap();
+            assert!(contract.verify_claim(id, key.to_string(), value.to_string(), encoding::encode_hex(salt), proof));
+        }
+        assert!(env.emitted_event(
+            &contract,
+            CredentialVerified {
+                credential_id: id,
+                verifier,
+                is_valid: true,
+                verification_type: "SELECTIVE_DISCLOSURE".to_string(),
+                timestamp: env.block_time(),
+            }
+        ));
+        
+        // Wrong value, wrong salt, tampered or foreign proof
+        let salt = encoding::encode_hex(&[2u8; 32]);
+        let proof = tree.proof_hex(1).unwrap();
+        let disclose = |contract: &mut CasperCredIQHostRef, id: U256, value: &str, salt: &str, proof: Vec<String>| {
+            contract.verify_claim(id, "role".to_string(), value.to_string(), salt.to_string(), proof)
+        };
+        assert!(!disclose(&mut contract, id, "viewer", &salt, proof.clone()));
+        assert!(!disclose(&mut contract, id, "admin", &encoding::encode_hex(&[9u8; 32]), proof.clone()));
+        assert!(!disclose(&mut contract, id, "admin", "zz", proof.clone()));
+        assert!(!disclose(&mut contract, id, "admin", &salt, tree.proof_hex(0).unwrap()));
+        assert!(!disclose(&mut contract, id, "admin", &salt, proof[1..].to_vec()));
+        assert!(!disclose(&mut contract, id, "admin", &salt, vec!["not-hex".to_string()]));
+        assert!(!disclose(&mut contract, without_root, "admin", &salt, proof.clone()));
+        assert!(!disclose(&mut contract, U256::from(99), "admin", &salt, proof.clone()));
+        
+        // Disclosure does not outlive the credential
+        env.set_caller(issuer);
+        contract.revoke_credential(id, "Left the company".to_string());
+        env.set_caller(verifier);
+        assert!(!disclose(&mut contract, id, "admin", &salt, proof));
+        assert!(env.emitted_event(
+            &contract,
+            CredentialVerified {
+                credential_id: id,
+                verifier,
+                is_valid: false,
+                verification_type: "SELECTIVE_DISCLOSURE".to_string(),
+                timestamp: env.block_time(),
+            }
+        ));
     }
 
     #[test]
-    fn test_issue_and_verify() {
+    fn test_revocation_status_list() {
         let env = odra_test::env();
         let issuer = env.get_account(0);
         let holder = env.get_account(1);
         
         env.set_caller(issuer);
         let mut contract = CasperCredIQ::deploy(&env, NoArgs);
-        contract.set_access_level(issuer, 2);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
+        assert_eq!(contract.get_status_list_length(), 0);
+        assert!(contract.get_status_list_chunk(0, 1024).is_empty());
         
-        let id = contract.issue_credential(
+        let inputs: Vec<CredentialInput> = (0..12)
+            .map(|n| CredentialInput {
+                issuer_did: "did:casper:issuer".to_string(),
+                holder_did: account_did(&holder),
+                holder_address: holder,
+                credential_hash: numbered_credential_hash(n),
+                issuer_signature: sign_hash(&env, issuer, &numbered_credential_hash(n)),
+                ipfs_hash: valid_ipfs_hash(),
+                ai_assessment_id: assess(&env, &mut contract, &numbered_credential_hash(n), 90),
+                expires_in_days: 365,
+                schema_id,
+                valid_from: None,
+                expires_at: None,
+                ai_provenance: None,
+                claims: Vec::new(),
+                claims_root: None,
+                required_endorsements: 0,
+            })
+            .collect();
+        let ids = contract.issue_credentials_batch(inputs);
+        
+        // Indexes are handed out in issuance order
+        assert_eq!(contract.get_status_list_length(), 12);
+        for (index, id) in ids.iter().enumerate() {
+            assert_eq!(contract.get_credential(*id).unwrap().status_list_index, index as u32);
+        }
+        assert_eq!(contract.get_status_list_chunk(0, 1024).to_vec(), vec![0, 0]);
+        
+        contract.revoke_credential(ids[0], "Test".to_string());
+        contract.revoke_credential(ids[9], "Test".to_string());
+        let assessment = assess(&env, &mut contract, &numbered_credential_hash(12), 90);
+        let replacement = contract.supersede_credential(
+            ids[3],
             "did:casper:issuer".to_string(),
-            "did:casper:holder".to_string(),
+            account_did(&holder),
             holder,
-            valid_credential_hash(),
-            valid_signature(),
+            numbered_credential_hash(12),
+            sign_hash(&env, issuer, &numbered_credential_hash(12)),
             valid_ipfs_hash(),
-            90,
+            assessment,
             365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
         );
         
-        assert_eq!(id, U256::zero());
+        // Bit i is the most significant bit of byte i / 8
+        assert_eq!(contract.get_credential(replacement).unwrap().status_list_index, 12);
+        assert_eq!(contract.get_status_list_length(), 13);
+        assert_eq!(contract.get_status_list_chunk(0, 1024).to_vec(), vec![0b1001_0000, 0b0100_0000]);
+        assert_eq!(contract.get_status_list_chunk(1, 1).to_vec(), vec![0b0100_0000]);
+        assert!(contract.get_status_list_chunk(2, 1024).is_empty());
+        for (index, id) in ids.iter().enumerate() {
+            assert_eq!(contract.is_revoked(*id), matches!(index, 0 | 3 | 9));
+        }
+    }
+
+    #[test]
+    fn test_status_list_word_boundaries() {
+        let env = odra_test::env();
+        let mut list = status_list::StatusList::deploy(&env, NoArgs);
+        for _ in 0..600 {
+            list.assign();
+        }
+        for index in [0, 255, 256, 599] {
+            list.set_revoked(index);
+        }
         
-        let vc = contract.get_credential(id).unwrap();
-        assert_eq!(vc.ai_confidence, 90);
-        assert!(!vc.revoked);
+        let bits = list.chunk(0, 1024);
+        assert_eq!(bits.len(), 75);
+        assert_eq!((bits[0], bits[31], bits[32], bits[74]), (0x80, 0x01, 0x80, 0x01));
+        assert_eq!(bits.iter().map(|b| b.count_ones()).sum::<u32>(), 4);
+        assert_eq!(list.chunk(30, 4).as_slice(), &bits[30..34]);
+        assert_eq!(list.chunk(70, 1024).as_slice(), &bits[70..]);
+        assert!(list.is_revoked(256) && !list.is_revoked(257));
+    }
+
+    /// Inverse of `encode_status_list`: strips the multibase prefix, decodes
+    /// and gunzips.
+    fn decode_status_list(encoded: &str) -> Vec<u8> {
+        use base64::Engine;
+        use std::io::Read;
         
-        assert!(contract.verify_credential(id));
+        let compressed = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded.strip_prefix('u').unwrap())
+            .unwrap();
+        let mut bitstring = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut bitstring).unwrap();
+        bitstring
     }
 
     #[test]
-    fn test_revocation() {
+    fn test_status_list_export() {
+        // Short lists are padded with zeros to the 16KB minimum
+        let bits = vec![0b1001_0000, 0b0100_0000];
+        let encoded = encode_status_list(&bits);
+        assert!(encoded.starts_with('u'));
+        assert!(!encoded.contains(['=', '+', '/']));
+        let decoded = decode_status_list(&encoded);
+        assert_eq!(decoded.len(), MIN_STATUS_LIST_BYTES);
+        assert_eq!(&decoded[..2], bits.as_slice());
+        assert!(decoded[2..].iter().all(|byte| *byte == 0));
+        assert_eq!(decode_status_list(&encode_status_list(&[])), vec![0; MIN_STATUS_LIST_BYTES]);
+        
+        // Longer lists round-trip unchanged
+        let long: Vec<u8> = (0..MIN_STATUS_LIST_BYTES + 3).map(|i| (i % 251) as u8).collect();
+        assert_eq!(decode_status_list(&encode_status_list(&long)), long);
+        
+        let credential = status_list_credential("https://example.com/status/1", "did:casper:issuer", &bits);
+        assert_eq!(credential["id"], "https://example.com/status/1");
+        assert_eq!(credential["issuer"], "did:casper:issuer");
+        assert_eq!(credential["type"][1], "BitstringStatusListCredential");
+        assert_eq!(credential["credentialSubject"]["id"], "https://example.com/status/1#list");
+        assert_eq!(credential["credentialSubject"]["statusPurpose"], "revocation");
+        assert_eq!(credential["credentialSubject"]["encodedList"], encoded);
+    }
+
+    /// Serialized size of the hash and signature stored for one credential, as
+    /// hex strings versus raw bytes, and, on the CasperVM backend
+    /// (`ODRA_BACKEND=casper`, with `wasm/CasperCredIQ.wasm` built), the gas of
+    /// the `issue_credential` call. OdraVM reports no gas, so the gas check
+    /// only runs there.
+    #[test]
+    fn test_compact_storage_size() {
+        // Same call, measured on the CasperVM backend with the hash, signature
+        // and hash index stored as hex strings (this layout: 7_063_511_658)
+        const HEX_LAYOUT_ISSUE_GAS: u64 = 7_214_490_798;
+        
         let env = odra_test::env();
         let issuer = env.get_account(0);
         let holder = env.get_account(1);
         
         env.set_caller(issuer);
         let mut contract = CasperCredIQ::deploy(&env, NoArgs);
-        contract.set_access_level(issuer, 2);
+        contract.set_access_level(issuer, 2, Maybe::None);
+        register_test_issuer(&env, &mut contract, issuer);
+        let schema_id = register_test_schema(&mut contract);
         
+        let credential_hash = "AB".repeat(32);
+        let assessment = assess(&env, &mut contract, &credential_hash, 90);
         let id = contract.issue_credential(
             "did:casper:issuer".to_string(),
-            "did:casper:holder".to_string(),
+            account_did(&holder),
             holder,
-            valid_credential_hash(),
-            valid_signature(),
+            credential_hash.clone(),
+            sign_hash(&env, issuer, &credential_hash),
             valid_ipfs_hash(),
-            95,
+            assessment,
             365,
+            schema_id,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
+            Maybe::None,
         );
+        let vc = contract.get_credential(id).unwrap();
         
-        contract.revoke_credential(id, "Test".to_string());
-        assert!(contract.is_revoked(id));
-        assert!(!contract.verify_credential(id));
+        // Clients keep passing and reading hex strings
+        assert_eq!(vc.credential_hash_hex(), credential_hash.to_ascii_lowercase());
+        assert_eq!(vc.issuer_signature_hex(), sign_hash(&env, issuer, &credential_hash));
+        assert_eq!(contract.get_credential_id_by_hash(credential_hash.clone()), Some(id));
+        assert!(contract.verify_credential_cryptographic(id, credential_hash.to_ascii_lowercase(), "HASH".to_string()));
+        
+        // Credential fields plus the hash -> id index key
+        let before = 2 * vc.credential_hash_hex().serialized_length() + vc.issuer_signature_hex().serialized_length();
+        let after = 2 * vc.credential_hash.serialized_length() + vc.issuer_signature.serialized_length();
+        assert_eq!(before, 2 * 68 + 134);
+        assert_eq!(after, 2 * 32 + 65);
+        assert!(after * 2 < before);
+        
+        let issue_gas = env.gas_report().into_iter().find_map(|report| match report {
+            DeployReport::ContractCall { gas, call_def, .. } if call_def.entry_point() == "issue_credential" => Some(gas),
+            _ => None,
+        });
+        if let Some(gas) = issue_gas.filter(|gas| !gas.is_zero()) {
+            assert!(gas < U512::from(HEX_LAYOUT_ISSUE_GAS));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_hex_hash_round_trips(hash in any::<[u8; 32]>(), upper in any::<bool>()) {
+            let hex = encoding::encode_hex(&hash);
+            let hex = if upper { hex.to_ascii_uppercase() } else { hex };
+            prop_assert_eq!(validation::parse_hex_hash(&hex), Ok(hash));
+        }
+
+        #[test]
+        fn prop_hex_hash_needs_64_chars(input in "[0-9a-f]{0,100}") {
+            prop_assume!(input.len() != 64);
+            prop_assert_eq!(validation::parse_hex_hash(&input), Err(HashError::InvalidLength));
+        }
+
+        #[test]
+        fn prop_valid_dids_parse(
+            method in "[a-z0-9]{1,12}",
+            segments in prop::collection::vec("([A-Za-z0-9._-]|%[0-9A-Fa-f]{2}){0,8}", 0..3),
+            last in "([A-Za-z0-9._-]|%[0-9A-Fa-f]{2}){1,16}",
+        ) {
+            let mut identifier = segments.join(":");
+            if !segments.is_empty() {
+                identifier.push(':');
+            }
+            identifier.push_str(&last);
+            let did = format!("did:{}:{}", method, identifier);
+            
+            let parsed = validation::parse_did(&did).unwrap();
+            prop_assert_eq!(parsed.method, method.as_str());
+            prop_assert_eq!(parsed.identifier, identifier.as_str());
+        }
+
+        #[test]
+        fn prop_dids_with_bad_characters_fail(prefix in "[a-z]{1,8}", bad in "[ #/?@!\\[\\]]") {
+            let did = format!("did:casper:{}{}", prefix, bad);
+            prop_assert_eq!(validation::parse_did(&did), Err(DidError::InvalidIdentifier));
+        }
+
+        #[test]
+        fn prop_parsers_never_panic(input in ".{0,80}") {
+            let _ = validation::parse_did(&input);
+            let _ = validation::parse_cid(&input);
+            let _ = validation::parse_hex_hash(&input);
+        }
+
+        #[test]
+        fn prop_cids_round_trip(codec in 0u8..0x80, digest in prop::collection::vec(any::<u8>(), 1..64)) {
+            let mut bytes = vec![1, codec, 0x12, digest.len() as u8];
+            bytes.extend(&digest);
+            let expected = validation::Cid { version: 1, codec: codec as u64, hash_code: 0x12, digest_len: digest.len() };
+            
+            prop_assert_eq!(validation::parse_cid(&format!("b{}", encode_base32(&bytes))), Ok(expected));
+            prop_assert_eq!(validation::parse_cid(&format!("z{}", encode_base58(&bytes))), Ok(expected));
+            prop_assert_eq!(validation::parse_cid(&format!("f{}", encoding::encode_hex(&bytes))), Ok(expected));
+            
+            // Dropping a digest byte breaks the multihash length
+            bytes.pop();
+            prop_assert_eq!(
+                validation::parse_cid(&format!("f{}", encoding::encode_hex(&bytes))),
+                Err(CidError::InvalidMultihash)
+            );
+        }
+
+        #[test]
+        fn prop_claim_tree_proofs_verify(
+            salts in prop::collection::vec(any::<[u8; 32]>(), 1..40),
+            index in any::<prop::sample::Index>(),
+        ) {
+            let values: Vec<String> = (0..salts.len()).map(|i| format!("value-{}", i)).collect();
+            let claims: Vec<(&str, &str, [u8; 32])> = values.iter().zip(&salts).map(|(v, s)| ("key", v.as_str(), *s)).collect();
+            let tree = merkle::ClaimTree::new(&claims).unwrap();
+            let i = index.index(claims.len());
+            let proof = tree.proof(i).unwrap();
+            
+            let leaf = merkle::leaf_hash("key", &values[i], &salts[i]);
+            prop_assert!(proof.len() <= merkle::MAX_PROOF_LEN);
+            prop_assert!(merkle::verify(&tree.root(), leaf, &proof));
+            prop_assert!(!merkle::verify(&tree.root(), merkle::leaf_hash("key", "other", &salts[i]), &proof));
+            prop_assert_eq!(tree.proof(claims.len()), None);
+        }
+
+        #[test]
+        fn prop_cid_v0_round_trips(digest in any::<[u8; 32]>()) {
+            let mut bytes = vec![0x12, 0x20];
+            bytes.extend(digest);
+            let cid = encode_base58(&bytes);
+            prop_assert_eq!(cid.len(), 46);
+            prop_assert_eq!(validation::parse_cid(&cid).map(|cid| cid.version), Ok(0));
+        }
     }
-}
\ No newline at end of file
+}
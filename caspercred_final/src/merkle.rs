@@ -0,0 +1,117 @@
+//! Merkle trees over salted credential claims, for selective disclosure.
+//!
+//! Each leaf commits to one `(key, value, salt)` claim; the issuer records the
+//! root at issuance and the holder later reveals a single claim together with
+//! its salt and inclusion proof. Sibling pairs are hashed in sorted order, so
+//! a proof is just the list of siblings from the leaf upwards. An unpaired
+//! node is carried up to the next level unchanged.
+//!
+//! `ClaimTree` builds roots and proofs on the host; the contract only needs
+//! `leaf_hash` and `verify`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::Digest;
+
+use crate::encoding;
+
+/// Longest proof `verify` accepts (a tree of up to 2^32 claims)
+pub const MAX_PROOF_LEN: usize = 32;
+
+/// Domain separators, so a leaf can never be passed off as an inner node
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash of one salted claim: BLAKE2b-256 over the prefix, the length-prefixed
+/// key and value, and the salt.
+pub fn leaf_hash(key: &str, value: &str, salt: &[u8; 32]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(1 + 4 + key.len() + 4 + value.len() + salt.len());
+    data.push(LEAF_PREFIX);
+    data.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    data.extend_from_slice(key.as_bytes());
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+    data.extend_from_slice(salt);
+    Digest::hash(data).value()
+}
+
+fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = [0u8; 65];
+    data[0] = NODE_PREFIX;
+    data[1..33].copy_from_slice(left);
+    data[33..].copy_from_slice(right);
+    Digest::hash(data).value()
+}
+
+/// True when `proof` leads from `leaf` to `root`.
+pub fn verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof.len() <= MAX_PROOF_LEN && proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling)) == *root
+}
+
+/// Host-side builder for claims roots and inclusion proofs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimTree {
+    /// Leaves first, root last
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl ClaimTree {
+    /// Builds the tree over `(key, value, salt)` claims, in the given order.
+    /// `None` when there are no claims.
+    pub fn new(claims: &[(&str, &str, [u8; 32])]) -> Option<Self> {
+        Self::from_leaves(claims.iter().map(|(key, value, salt)| leaf_hash(key, value, salt)).collect())
+    }
+
+    pub fn from_leaves(leaves: Vec<[u8; 32]>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+
+        let mut levels = alloc::vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Some(ClaimTree { levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// The root in the hex form issuance takes.
+    pub fn root_hex(&self) -> String {
+        encoding::encode_hex(&self.root())
+    }
+
+    /// Siblings of the leaf at `index`, from the bottom up.
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+
+    /// `proof` in the hex form `verify_claim` takes.
+    pub fn proof_hex(&self, index: usize) -> Option<Vec<String>> {
+        let proof = self.proof(index)?;
+        Some(proof.iter().map(|node| encoding::encode_hex(node)).collect())
+    }
+}
@@ -0,0 +1,40 @@
+use odra::prelude::*;
+
+/// Contract owner plus the owner proposed by an in-flight two-step transfer
+#[odra::module]
+pub struct Ownership {
+    owner: Var<Address>,
+    pending_owner: Var<Option<Address>>,
+}
+
+#[odra::module]
+impl Ownership {
+    pub fn init_owner(&mut self, owner: Address) {
+        self.owner.set(owner);
+        self.pending_owner.set(None);
+    }
+
+    pub fn get_owner(&self) -> Address {
+        self.owner.get().unwrap()
+    }
+
+    pub fn is_owner(&self, address: &Address) -> bool {
+        self.get_owner() == *address
+    }
+
+    pub fn get_pending_owner(&self) -> Option<Address> {
+        self.pending_owner.get().flatten()
+    }
+
+    pub fn set_pending_owner(&mut self, pending_owner: Option<Address>) {
+        self.pending_owner.set(pending_owner);
+    }
+
+    /// Promotes the pending owner and returns the previous owner.
+    pub fn complete_transfer(&mut self, new_owner: Address) -> Address {
+        let previous_owner = self.get_owner();
+        self.owner.set(new_owner);
+        self.pending_owner.set(None);
+        previous_owner
+    }
+}
@@ -0,0 +1,127 @@
+use odra::prelude::*;
+
+use crate::Error;
+
+/// Default cap on how far a renewal may push expiry past the renewal time (1 year)
+const DEFAULT_MAX_RENEWAL: u64 = 365 * 24 * 60 * 60 * 1000;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct AcceptanceRequirementChanged {
+    pub issuer: Address,
+    pub required: bool,
+    pub timestamp: u64,
+}
+
+/// `issuer` is `None` for the global threshold; `min_confidence` is `None`
+/// when an issuer override is cleared.
+#[odra::event]
+pub struct ConfidencePolicyChanged {
+    pub issuer: Option<Address>,
+    pub min_confidence: Option<u8>,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct MaxRenewalChanged {
+    pub old_max: u64,
+    pub new_max: u64,
+    pub changed_by: Address,
+    pub timestamp: u64,
+}
+
+// ================ MODULE ================
+
+/// Credential lifecycle settings: renewal limit, holder acceptance and
+/// AI-confidence review thresholds
+#[odra::module]
+pub struct CredentialPolicy {
+    max_renewal: Var<u64>,
+    acceptance_required: Mapping<Address, bool>,
+    min_confidence: Var<u8>,
+    issuer_min_confidence: Mapping<Address, Option<u8>>,
+}
+
+#[odra::module]
+impl CredentialPolicy {
+    /// Longest allowed distance (ms) between a renewal and the new expiry.
+    pub fn max_renewal(&self) -> u64 {
+        self.max_renewal.get().unwrap_or(DEFAULT_MAX_RENEWAL)
+    }
+
+    /// A value of 0 disables renewals.
+    pub fn set_max_renewal(&mut self, new_max: u64, changed_by: Address) {
+        let old_max = self.max_renewal();
+        self.max_renewal.set(new_max);
+
+        self.env().emit_event(MaxRenewalChanged {
+            old_max,
+            new_max,
+            changed_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Whether credentials from `issuer` start `Pending` until the holder accepts.
+    pub fn acceptance_required(&self, issuer: &Address) -> bool {
+        self.acceptance_required.get(issuer).unwrap_or(false)
+    }
+
+    pub fn set_acceptance_required(&mut self, issuer: Address, required: bool) {
+        self.acceptance_required.set(&issuer, required);
+
+        self.env().emit_event(AcceptanceRequirementChanged {
+            issuer,
+            required,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Confidence below which an issuer's credentials need review: the
+    /// issuer's override if set, otherwise the global threshold (default 0).
+    pub fn min_confidence(&self, issuer: &Address) -> u8 {
+        match self.issuer_min_confidence.get(issuer).flatten() {
+            Some(threshold) => threshold,
+            None => self.global_min_confidence(),
+        }
+    }
+
+    pub fn global_min_confidence(&self) -> u8 {
+        self.min_confidence.get().unwrap_or(0)
+    }
+
+    pub fn set_global_min_confidence(&mut self, min_confidence: u8, changed_by: Address) {
+        self.check_threshold(min_confidence);
+        self.min_confidence.set(min_confidence);
+
+        self.env().emit_event(ConfidencePolicyChanged {
+            issuer: None,
+            min_confidence: Some(min_confidence),
+            changed_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// `None` removes the override so the global threshold applies again.
+    pub fn set_issuer_min_confidence(&mut self, issuer: Address, min_confidence: Option<u8>, changed_by: Address) {
+        if let Some(threshold) = min_confidence {
+            self.check_threshold(threshold);
+        }
+        self.issuer_min_confidence.set(&issuer, min_confidence);
+
+        self.env().emit_event(ConfidencePolicyChanged {
+            issuer: Some(issuer),
+            min_confidence,
+            changed_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    fn check_threshold(&self, min_confidence: u8) {
+        if min_confidence > 100 {
+            self.env().revert(Error::InvalidInput);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use odra::prelude::*;
+
+/// Length of a rate-limit window (1 hour)
+const WINDOW: u64 = 60 * 60 * 1000;
+
+/// Credentials an address may issue one at a time per window
+const MAX_SINGLE_ISSUES: u32 = 25;
+
+/// Credentials an address may issue through batches per window
+const MAX_BATCH_ISSUES: u32 = 500;
+
+/// Rate Limit Data (combines last_issue_time + issue_count)
+#[odra::odra_type]
+pub struct RateLimitData {
+    pub last_issue_time: u64,
+    pub issue_count: u32,
+}
+
+/// Issuance quotas per address. Single and batch issuance are counted separately.
+#[odra::module]
+pub struct RateLimiter {
+    single: Mapping<Address, RateLimitData>,
+    batch: Mapping<Address, RateLimitData>,
+}
+
+#[odra::module]
+impl RateLimiter {
+    /// Records `count` single issues; returns `false` (recording nothing) if over quota.
+    pub fn record_single(&mut self, issuer: &Address, count: u32) -> bool {
+        let data = self.single.get(issuer);
+        match self.consume(data, count, MAX_SINGLE_ISSUES) {
+            Some(data) => {
+                self.single.set(issuer, data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Records `count` batch-issued credentials; returns `false` (recording nothing) if over quota.
+    pub fn record_batch(&mut self, issuer: &Address, count: u32) -> bool {
+        let data = self.batch.get(issuer);
+        match self.consume(data, count, MAX_BATCH_ISSUES) {
+            Some(data) => {
+                self.batch.set(issuer, data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The count resets once a full window has passed since the last issue.
+    fn consume(&self, data: Option<RateLimitData>, count: u32, limit: u32) -> Option<RateLimitData> {
+        let current_time = self.env().get_block_time();
+        let mut data = data.unwrap_or(RateLimitData {
+            last_issue_time: 0,
+            issue_count: 0,
+        });
+
+        if current_time - data.last_issue_time > WINDOW {
+            data.issue_count = 0;
+        }
+
+        let issue_count = data.issue_count.checked_add(count).filter(|c| *c <= limit)?;
+        Some(RateLimitData {
+            last_issue_time: current_time,
+            issue_count,
+        })
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+use crate::validation;
+use crate::Error;
+
+/// Most requests returned by one `pending` call
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Open (pending) requests allowed per requester
+const MAX_OPEN_PER_REQUESTER: u32 = 10;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct CredentialRequested {
+    pub request_id: u32,
+    pub requester: Address,
+    pub issuer_did: String,
+    pub credential_type: String,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialRequestApproved {
+    pub request_id: u32,
+    pub credential_id: U256,
+    pub approved_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct CredentialRequestRejected {
+    pub request_id: u32,
+    pub rejected_by: Address,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum RequestStatus {
+    Pending = 0,
+    Approved = 1,
+    Rejected = 2,
+}
+
+/// A holder's request for a credential from a registered issuer DID
+#[odra::odra_type]
+pub struct CredentialRequest {
+    pub request_id: u32,
+    pub requester: Address,
+    pub issuer_did: String,
+    pub holder_did: String,
+    pub credential_type: String,
+    pub evidence_ipfs_hash: String,
+    pub status: RequestStatus,
+    pub submitted_at: u64,
+    pub updated_at: u64,
+    /// Set once approved.
+    pub credential_id: Option<U256>,
+    /// Set once rejected.
+    pub rejection_reason: String,
+}
+
+// ================ MODULE ================
+
+/// Credential request queue. Pending requests are listed per issuer DID;
+/// a resolved request is swapped out of its issuer's pending list.
+#[odra::module]
+pub struct RequestQueue {
+    requests: Mapping<u32, CredentialRequest>,
+    request_counter: Var<u32>,
+    pending_at: Mapping<(String, u32), u32>,
+    pending_position: Mapping<u32, u32>,
+    pending_count: Mapping<String, u32>,
+    open_by_requester: Mapping<Address, u32>,
+}
+
+#[odra::module]
+impl RequestQueue {
+    pub fn submit(
+        &mut self,
+        requester: Address,
+        issuer_did: String,
+        holder_did: String,
+        credential_type: String,
+        evidence_ipfs_hash: String,
+    ) -> u32 {
+        if credential_type.is_empty() || credential_type.len() > 64 {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        if let Err(error) = validation::parse_cid(&evidence_ipfs_hash) {
+            self.env().revert(Error::from(error));
+        }
+
+        let open = self.open_by_requester.get(&requester).unwrap_or(0);
+        if open >= MAX_OPEN_PER_REQUESTER {
+            self.env().revert(Error::RateLimitExceeded);
+        }
+        self.open_by_requester.set(&requester, open + 1);
+
+        let request_id = self.request_counter.get().unwrap_or(0);
+        self.request_counter.set(request_id + 1);
+
+        let current_time = self.env().get_block_time();
+        self.requests.set(&request_id, CredentialRequest {
+            request_id,
+            requester,
+            issuer_did: issuer_did.clone(),
+            holder_did,
+            credential_type: credential_type.clone(),
+            evidence_ipfs_hash,
+            status: RequestStatus::Pending,
+            submitted_at: current_time,
+            updated_at: current_time,
+            credential_id: None,
+            rejection_reason: String::new(),
+        });
+
+        let position = self.pending_count(issuer_did.clone());
+        self.pending_at.set(&(issuer_did.clone(), position), request_id);
+        self.pending_position.set(&request_id, position);
+        self.pending_count.set(&issuer_did, position + 1);
+
+        self.env().emit_event(CredentialRequested {
+            request_id,
+            requester,
+            issuer_did,
+            credential_type,
+            timestamp: current_time,
+        });
+
+        request_id
+    }
+
+    pub fn mark_approved(&mut self, request_id: u32, credential_id: U256, approved_by: Address) {
+        let mut request = self.pending_or_revert(request_id);
+        let current_time = self.env().get_block_time();
+
+        request.status = RequestStatus::Approved;
+        request.credential_id = Some(credential_id);
+        request.updated_at = current_time;
+        self.close(request);
+
+        self.env().emit_event(CredentialRequestApproved {
+            request_id,
+            credential_id,
+            approved_by,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn mark_rejected(&mut self, request_id: u32, reason: String, rejected_by: Address) {
+        let mut request = self.pending_or_revert(request_id);
+        let current_time = self.env().get_block_time();
+
+        request.status = RequestStatus::Rejected;
+        request.rejection_reason = reason.clone();
+        request.updated_at = current_time;
+        self.close(request);
+
+        self.env().emit_event(CredentialRequestRejected {
+            request_id,
+            rejected_by,
+            reason,
+            timestamp: current_time,
+        });
+    }
+
+    pub fn get(&self, request_id: u32) -> Option<CredentialRequest> {
+        self.requests.get(&request_id)
+    }
+
+    pub fn pending_or_revert(&self, request_id: u32) -> CredentialRequest {
+        let request = match self.get(request_id) {
+            Some(r) => r,
+            None => self.env().revert(Error::RequestNotFound),
+        };
+
+        if request.status != RequestStatus::Pending {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        request
+    }
+
+    pub fn pending_count(&self, issuer_did: String) -> u32 {
+        self.pending_count.get(&issuer_did).unwrap_or(0)
+    }
+
+    /// Pending requests for `issuer_did` at positions `[offset, offset + limit)`,
+    /// at most `MAX_PAGE_SIZE`. Positions shift as requests are resolved.
+    pub fn pending(&self, issuer_did: String, offset: u32, limit: u32) -> Vec<CredentialRequest> {
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(self.pending_count(issuer_did.clone()));
+        (offset..end)
+            .filter_map(|i| self.pending_at.get(&(issuer_did.clone(), i)))
+            .filter_map(|request_id| self.requests.get(&request_id))
+            .collect()
+    }
+
+    /// Stores a resolved request and swap-removes it from the pending list.
+    fn close(&mut self, request: CredentialRequest) {
+        let request_id = request.request_id;
+        let issuer_did = request.issuer_did.clone();
+        let requester = request.requester;
+        self.requests.set(&request_id, request);
+
+        let position = self.pending_position.get(&request_id).unwrap_or(0);
+        let last = self.pending_count(issuer_did.clone()) - 1;
+        if position != last {
+            let moved = self.pending_at.get(&(issuer_did.clone(), last)).unwrap();
+            self.pending_at.set(&(issuer_did.clone(), position), moved);
+            self.pending_position.set(&moved, position);
+        }
+        self.pending_count.set(&issuer_did, last);
+
+        let open = self.open_by_requester.get(&requester).unwrap_or(0);
+        self.open_by_requester.set(&requester, open.saturating_sub(1));
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::prelude::*;
+
+use crate::validation;
+use crate::Error;
+
+/// Most schemas returned by one `list` call
+const MAX_PAGE_SIZE: u32 = 50;
+
+// ================ EVENTS ================
+
+#[odra::event]
+pub struct SchemaRegistered {
+    pub schema_id: u32,
+    pub name: String,
+    pub version: String,
+    pub schema_hash: String,
+    pub registered_by: Address,
+    pub timestamp: u64,
+}
+
+#[odra::event]
+pub struct SchemaDeprecated {
+    pub schema_id: u32,
+    pub deprecated_by: Address,
+    pub timestamp: u64,
+}
+
+// ================ DATA STRUCTURES ================
+
+/// Credential type definition; the JSON schema itself lives at `ipfs_uri`
+#[odra::odra_type]
+pub struct CredentialSchema {
+    pub schema_id: u32,
+    pub name: String,
+    pub version: String,
+    pub schema_hash: String,
+    pub ipfs_uri: String,
+    pub registered_by: Address,
+    pub registered_at: u64,
+    /// Deprecated schemas stay readable but cannot be used for new credentials.
+    pub deprecated: bool,
+}
+
+// ================ MODULE ================
+
+/// Versioned credential schemas. A (name, version) pair can be registered once.
+#[odra::module]
+pub struct SchemaRegistry {
+    schemas: Mapping<u32, CredentialSchema>,
+    schema_ids: Mapping<(String, String), u32>,
+    schema_count: Var<u32>,
+    credential_count: Mapping<u32, u32>,
+}
+
+#[odra::module]
+impl SchemaRegistry {
+    pub fn register(
+        &mut self,
+        name: String,
+        version: String,
+        schema_hash: String,
+        ipfs_uri: String,
+        registered_by: Address,
+    ) -> u32 {
+        if name.is_empty() || version.is_empty() || validation::parse_hex_hash(&schema_hash).is_err() {
+            self.env().revert(Error::InvalidInput);
+        }
+
+        if let Err(error) = validation::parse_cid(&ipfs_uri) {
+            self.env().revert(Error::from(error));
+        }
+
+        let key = (name.clone(), version.clone());
+        if self.schema_ids.get(&key).is_some() {
+            self.env().revert(Error::AlreadyExists);
+        }
+
+        let schema_id = self.count();
+        let current_time = self.env().get_block_time();
+        self.schemas.set(&schema_id, CredentialSchema {
+            schema_id,
+            name: name.clone(),
+            version: version.clone(),
+            schema_hash: schema_hash.clone(),
+            ipfs_uri,
+            registered_by,
+            registered_at: current_time,
+            deprecated: false,
+        });
+        self.schema_ids.set(&key, schema_id);
+        self.schema_count.set(schema_id + 1);
+
+        self.env().emit_event(SchemaRegistered {
+            schema_id,
+            name,
+            version,
+            schema_hash,
+            registered_by,
+            timestamp: current_time,
+        });
+
+        schema_id
+    }
+
+    pub fn deprecate(&mut self, schema_id: u32, deprecated_by: Address) {
+        let mut schema = self.get_or_revert(schema_id);
+
+        if schema.deprecated {
+            self.env().revert(Error::SchemaDeprecated);
+        }
+
+        schema.deprecated = true;
+        self.schemas.set(&schema_id, schema);
+
+        self.env().emit_event(SchemaDeprecated {
+            schema_id,
+            deprecated_by,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Counts a new credential against `schema_id`.
+    pub fn record_credential(&mut self, schema_id: u32) {
+        let count = self.credential_count(schema_id);
+        self.credential_count.set(&schema_id, count + 1);
+    }
+
+    pub fn get(&self, schema_id: u32) -> Option<CredentialSchema> {
+        self.schemas.get(&schema_id)
+    }
+
+    pub fn get_or_revert(&self, schema_id: u32) -> CredentialSchema {
+        match self.get(schema_id) {
+            Some(schema) => schema,
+            None => self.env().revert(Error::SchemaNotFound),
+        }
+    }
+
+    /// Reverts unless `schema_id` exists and is not deprecated.
+    pub fn check_usable(&self, schema_id: u32) {
+        if self.get_or_revert(schema_id).deprecated {
+            self.env().revert(Error::SchemaDeprecated);
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.schema_count.get().unwrap_or(0)
+    }
+
+    pub fn credential_count(&self, schema_id: u32) -> u32 {
+        self.credential_count.get(&schema_id).unwrap_or(0)
+    }
+
+    /// Schemas with ids in `[offset, offset + limit)`, at most `MAX_PAGE_SIZE`.
+    pub fn list(&self, offset: u32, limit: u32) -> Vec<CredentialSchema> {
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.count());
+        (offset..end).filter_map(|id| self.schemas.get(&id)).collect()
+    }
+}
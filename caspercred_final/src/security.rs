@@ -0,0 +1,39 @@
+use odra::prelude::*;
+
+/// Verification Data (combines verification_count + blocked_until)
+#[odra::odra_type]
+pub struct VerificationData {
+    pub verification_count: u32,
+    pub blocked_until: u64,
+}
+
+/// Per-address security tracking: verification throttling and suspicious
+/// activity counts
+#[odra::module]
+pub struct SecurityMonitor {
+    verification_data: Mapping<Address, VerificationData>,
+    suspicious_activity: Mapping<Address, u32>,
+}
+
+#[odra::module]
+impl SecurityMonitor {
+    pub fn verification_data(&self, address: &Address) -> VerificationData {
+        self.verification_data.get(address).unwrap_or(VerificationData {
+            verification_count: 0,
+            blocked_until: 0,
+        })
+    }
+
+    pub fn set_verification_data(&mut self, address: &Address, data: VerificationData) {
+        self.verification_data.set(address, data);
+    }
+
+    pub fn suspicious_count(&self, address: &Address) -> u32 {
+        self.suspicious_activity.get(address).unwrap_or(0)
+    }
+
+    pub fn record_suspicious(&mut self, address: &Address) {
+        let count = self.suspicious_count(address);
+        self.suspicious_activity.set(address, count + 1);
+    }
+}
@@ -0,0 +1,74 @@
+//! Revocation status as a W3C Bitstring Status List: one bit per credential,
+//! at the status list index assigned when it was issued. Bit 0 is the most
+//! significant bit of the first byte, as the spec requires.
+
+use alloc::vec::Vec;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::prelude::*;
+
+/// Bytes per stored word (256 status bits)
+pub const WORD_BYTES: u32 = 32;
+
+/// Most bytes returned by one `chunk` call (8192 statuses)
+pub const MAX_CHUNK_BYTES: u32 = 1024;
+
+/// Packed revocation bits, stored 256 to a word
+#[odra::module]
+pub struct StatusList {
+    words: Mapping<u32, [u8; 32]>,
+    length: Var<u32>,
+}
+
+#[odra::module]
+impl StatusList {
+    /// Hands out the next index; the matching bit starts cleared.
+    pub fn assign(&mut self) -> u32 {
+        let index = self.length();
+        self.length.set(index + 1);
+        index
+    }
+
+    /// Number of indexes assigned so far.
+    pub fn length(&self) -> u32 {
+        self.length.get().unwrap_or(0)
+    }
+
+    pub fn set_revoked(&mut self, index: u32) {
+        let word_index = index / (WORD_BYTES * 8);
+        let mut word = self.words.get(&word_index).unwrap_or([0u8; 32]);
+        let (byte, mask) = bit_position(index);
+        word[byte] |= mask;
+        self.words.set(&word_index, word);
+    }
+
+    pub fn is_revoked(&self, index: u32) -> bool {
+        let (byte, mask) = bit_position(index);
+        self.words
+            .get(&(index / (WORD_BYTES * 8)))
+            .is_some_and(|word| word[byte] & mask != 0)
+    }
+
+    /// Up to `max_bytes` (capped at `MAX_CHUNK_BYTES`) of the bitstring from
+    /// byte `offset`, stopping at the last byte holding an assigned index.
+    pub fn chunk(&self, offset: u32, max_bytes: u32) -> Bytes {
+        let total_bytes = self.length().div_ceil(8);
+        let end = total_bytes.min(offset.saturating_add(max_bytes.min(MAX_CHUNK_BYTES)));
+
+        let mut bytes = Vec::with_capacity(end.saturating_sub(offset) as usize);
+        let mut position = offset;
+        while position < end {
+            let word = self.words.get(&(position / WORD_BYTES)).unwrap_or([0u8; 32]);
+            let start = (position % WORD_BYTES) as usize;
+            let take = ((end - position) as usize).min(WORD_BYTES as usize - start);
+            bytes.extend_from_slice(&word[start..start + take]);
+            position += take as u32;
+        }
+        Bytes::from(bytes)
+    }
+}
+
+/// Byte within its word, and the bit mask, of status `index`.
+fn bit_position(index: u32) -> (usize, u8) {
+    let byte = (index % (WORD_BYTES * 8)) / 8;
+    (byte as usize, 0x80 >> (index % 8))
+}
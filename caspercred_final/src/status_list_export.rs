@@ -0,0 +1,50 @@
+//! Host-side export of the revocation bitstring as a W3C
+//! `BitstringStatusListCredential`, for tooling such as the CLI. Not part of
+//! the contract (wasm) build.
+
+extern crate std;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::json;
+use std::io::Write;
+
+/// Smallest bitstring the spec allows (16KB), so that a small list still
+/// hides which credential a verifier is checking
+pub const MIN_STATUS_LIST_BYTES: usize = 131_072 / 8;
+
+/// `encodedList` form of a bitstring: padded with zeros to the 16KB minimum,
+/// GZIP-compressed and multibase base64url-encoded (`u` prefix, no padding).
+pub fn encode_status_list(bitstring: &[u8]) -> String {
+    let mut padded = bitstring.to_vec();
+    if padded.len() < MIN_STATUS_LIST_BYTES {
+        padded.resize(MIN_STATUS_LIST_BYTES, 0);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&padded).expect("in-memory write");
+    let compressed = encoder.finish().expect("in-memory write");
+
+    format!("u{}", URL_SAFE_NO_PAD.encode(compressed))
+}
+
+/// Unsigned revocation `BitstringStatusListCredential` published at `id`.
+pub fn status_list_credential(id: &str, issuer: &str, bitstring: &[u8]) -> serde_json::Value {
+    json!({
+        "@context": ["https://www.w3.org/ns/credentials/v2"],
+        "id": id,
+        "type": ["VerifiableCredential", "BitstringStatusListCredential"],
+        "issuer": issuer,
+        "credentialSubject": {
+            "id": format!("{}#list", id),
+            "type": "BitstringStatusList",
+            "statusPurpose": "revocation",
+            "encodedList": encode_status_list(bitstring),
+        },
+    })
+}
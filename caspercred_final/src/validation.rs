@@ -0,0 +1,253 @@
+//! Syntax checks for DIDs, IPFS CIDs and hex hashes, shared by the contract
+//! and host-side tooling.
+
+use alloc::vec::Vec;
+
+use crate::encoding;
+use crate::Error;
+
+/// Multicodec code of sha2-256, the only hash a CIDv0 may use
+const SHA2_256: u64 = 0x12;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+// ================ ERRORS ================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DidError {
+    /// Does not start with `did:`.
+    MissingScheme,
+    /// Method name is empty or not lowercase letters and digits.
+    InvalidMethod,
+    /// Method-specific id is empty, ends with `:` or has a character
+    /// outside `ALPHA / DIGIT / "." / "-" / "_" / pct-encoded`.
+    InvalidIdentifier,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CidError {
+    Empty,
+    /// Neither a CIDv0 nor one of the multibase prefixes `z`, `b`, `B`, `f`, `F`.
+    UnsupportedMultibase,
+    /// Characters outside the multibase alphabet, or a truncated varint.
+    InvalidEncoding,
+    UnsupportedVersion,
+    /// Multihash digest length does not match the remaining bytes.
+    InvalidMultihash,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashError {
+    /// Not exactly 64 characters.
+    InvalidLength,
+    NotHex,
+}
+
+impl From<DidError> for Error {
+    fn from(error: DidError) -> Self {
+        match error {
+            DidError::MissingScheme => Error::DidMissingScheme,
+            DidError::InvalidMethod => Error::DidInvalidMethod,
+            DidError::InvalidIdentifier => Error::DidInvalidIdentifier,
+        }
+    }
+}
+
+impl From<CidError> for Error {
+    fn from(error: CidError) -> Self {
+        match error {
+            CidError::Empty => Error::CidEmpty,
+            CidError::UnsupportedMultibase => Error::CidUnsupportedMultibase,
+            CidError::InvalidEncoding => Error::CidInvalidEncoding,
+            CidError::UnsupportedVersion => Error::CidUnsupportedVersion,
+            CidError::InvalidMultihash => Error::CidInvalidMultihash,
+        }
+    }
+}
+
+impl From<HashError> for Error {
+    fn from(error: HashError) -> Self {
+        match error {
+            HashError::InvalidLength => Error::HashInvalidLength,
+            HashError::NotHex => Error::HashNotHex,
+        }
+    }
+}
+
+// ================ DIDs ================
+
+/// `did:<method>:<method-specific-id>`, borrowed from the parsed string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Did<'a> {
+    pub method: &'a str,
+    pub identifier: &'a str,
+}
+
+/// Parses a DID per the W3C DID Core ABNF (no path, query or fragment).
+pub fn parse_did(input: &str) -> Result<Did<'_>, DidError> {
+    let rest = input.strip_prefix("did:").ok_or(DidError::MissingScheme)?;
+    let (method, identifier) = rest.split_once(':').ok_or(DidError::InvalidMethod)?;
+
+    if method.is_empty() || !method.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()) {
+        return Err(DidError::InvalidMethod);
+    }
+
+    if !is_method_specific_id(identifier.as_bytes()) {
+        return Err(DidError::InvalidIdentifier);
+    }
+
+    Ok(Did { method, identifier })
+}
+
+/// `*( *idchar ":" ) 1*idchar`
+fn is_method_specific_id(bytes: &[u8]) -> bool {
+    if bytes.last().is_none_or(|&b| b == b':') {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let escaped = bytes.get(i + 1..i + 3);
+                if !escaped.is_some_and(|pair| pair.iter().all(u8::is_ascii_hexdigit)) {
+                    return false;
+                }
+                i += 3;
+            }
+            b if b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_' | b':') => i += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+// ================ IPFS CIDs ================
+
+/// Decoded CID header; `codec` is `dag-pb` (0x70) for CIDv0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cid {
+    pub version: u8,
+    pub codec: u64,
+    pub hash_code: u64,
+    pub digest_len: usize,
+}
+
+/// Parses a CIDv0 (`Qm...`, base58btc sha2-256) or a multibase CIDv1.
+pub fn parse_cid(input: &str) -> Result<Cid, CidError> {
+    if input.is_empty() {
+        return Err(CidError::Empty);
+    }
+
+    if input.len() == 46 && input.starts_with("Qm") {
+        let bytes = decode_base58(input.as_bytes()).ok_or(CidError::InvalidEncoding)?;
+        let (hash_code, digest_len) = parse_multihash(&bytes)?;
+        if hash_code != SHA2_256 || digest_len != 32 {
+            return Err(CidError::InvalidMultihash);
+        }
+        return Ok(Cid { version: 0, codec: 0x70, hash_code, digest_len });
+    }
+
+    // The prefixes are ASCII, so slicing after them stays on a char boundary
+    let bytes = match input.as_bytes()[0] {
+        b'z' => decode_base58(&input.as_bytes()[1..]),
+        b'b' => decode_base32(&input.as_bytes()[1..]),
+        b'B' => decode_base32(input[1..].to_ascii_lowercase().as_bytes()),
+        b'f' | b'F' => encoding::decode_hex(&input[1..]),
+        _ => return Err(CidError::UnsupportedMultibase),
+    }
+    .ok_or(CidError::InvalidEncoding)?;
+
+    let (version, rest) = read_varint(&bytes).ok_or(CidError::InvalidEncoding)?;
+    if version != 1 {
+        return Err(CidError::UnsupportedVersion);
+    }
+    let (codec, multihash) = read_varint(rest).ok_or(CidError::InvalidEncoding)?;
+    let (hash_code, digest_len) = parse_multihash(multihash)?;
+
+    Ok(Cid { version: 1, codec, hash_code, digest_len })
+}
+
+/// Returns `(hash code, digest length)` of a multihash spanning all of `bytes`.
+fn parse_multihash(bytes: &[u8]) -> Result<(u64, usize), CidError> {
+    let (hash_code, rest) = read_varint(bytes).ok_or(CidError::InvalidMultihash)?;
+    let (digest_len, digest) = read_varint(rest).ok_or(CidError::InvalidMultihash)?;
+
+    if digest_len == 0 || digest.len() as u64 != digest_len {
+        return Err(CidError::InvalidMultihash);
+    }
+    Ok((hash_code, digest.len()))
+}
+
+/// Unsigned LEB128, at most 9 bytes as the multiformats spec requires.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+fn decode_base58(input: &[u8]) -> Option<Vec<u8>> {
+    let leading_zeros = input.iter().take_while(|&&c| c == b'1').count();
+
+    // Little-endian base-256 digits of the value
+    let mut digits: Vec<u8> = Vec::new();
+    for &c in input {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) * 58;
+            *digit = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut bytes = alloc::vec![0u8; leading_zeros];
+    bytes.extend(digits.iter().rev().skip_while(|&&d| d == 0));
+    Some(bytes)
+}
+
+/// RFC 4648 base32, lowercase, without padding.
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &c in input {
+        buffer = (buffer << 5) | BASE32_ALPHABET.iter().position(|&a| a == c)? as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // Leftover bits are padding: fewer than one character's worth, all zero
+    if bits >= 5 || buffer != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+// ================ HASHES ================
+
+/// Parses a 32-byte hash written as 64 hex characters (either case).
+pub fn parse_hex_hash(input: &str) -> Result<[u8; 32], HashError> {
+    if input.len() != 64 {
+        return Err(HashError::InvalidLength);
+    }
+
+    let bytes = encoding::decode_hex(input).ok_or(HashError::NotHex)?;
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
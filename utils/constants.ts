@@ -186,6 +186,8 @@ export interface VerifiableCredential {
   ai_confidence: number;
   ipfs_hash: string;
   revoked: boolean;
+  schema_id: number;
+  ai_assessment_id: number;
 }
 
 /**
@@ -225,8 +227,10 @@ export interface IssueCredentialParams {
   credential_hash: string;
   issuer_signature: string;
   ipfs_hash: string;
-  ai_confidence: number;
+  ai_assessment_id: number;
   expires_in_days: number;
+  schema_id: number;
+  required_endorsements?: number; // optional; endorsements needed before it verifies
 }
 
 /**
@@ -251,6 +255,8 @@ export interface CredentialIssuedEvent {
   credential_hash: string;
   ipfs_hash: string;
   timestamp: number;
+  schema_id: number;
+  ai_assessment_id: number;
 }
 
 export interface CredentialRevokedEvent {